@@ -0,0 +1,159 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use futures_executor::block_on;
+use rsa::RsaPrivateKey;
+use rsa_signature_2017::json_ld::SignedDocument;
+use rsa_signature_2017::{sign_rsa_signature_2017, verify_rsa_signature_2017, Signature, Verifier};
+use sophia_api::dataset::CollectibleDataset;
+use sophia_api::parser::QuadParser;
+use sophia_inmem::dataset::LightDataset;
+use sophia_iri::Iri;
+use sophia_turtle::parser::nq::NQuadsParser;
+
+const CREATOR: &str = "https://example.com/#me";
+
+fn parse_nq(input: &str) -> LightDataset {
+    LightDataset::from_quad_source(NQuadsParser {}.parse(input.as_bytes())).unwrap()
+}
+
+/// Returns the N-Quads of a `Note` with `n` tags.
+fn note_nq(n: usize) -> String {
+    let mut ret = String::from(
+        "_:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .\n\
+         _:b0 <https://www.w3.org/ns/activitystreams#content> \"Hello, world!\" .\n",
+    );
+    for i in 0..n {
+        ret.push_str(&format!(
+            "_:b0 <https://www.w3.org/ns/activitystreams#tag> <https://example.com/tags/{}> .\n",
+            i
+        ));
+    }
+    ret
+}
+
+/// Returns the JSON-LD representation of the same document as `note_nq(n)` with the `signatures`.
+fn note_json(n: usize, signatures: &[Signature<'_>]) -> String {
+    let tags = (0..n)
+        .map(|i| format!("\"https://example.com/tags/{}\"", i))
+        .collect::<Vec<_>>()
+        .join(",");
+    let signatures = signatures
+        .iter()
+        .map(|signature| {
+            format!(
+                r#"{{
+                    "type": "RsaSignature2017",
+                    "created": "{}",
+                    "creator": "{}",
+                    "nonce": "{}",
+                    "signatureValue": "{}"
+                }}"#,
+                signature.created,
+                signature.creator,
+                signature.nonce.as_deref().unwrap(),
+                signature.signature_value_base64(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{
+            "@context": [
+                "https://w3id.org/security/v1",
+                {{
+                    "content": "https://www.w3.org/ns/activitystreams#content",
+                    "tag": {{ "@id": "https://www.w3.org/ns/activitystreams#tag", "@type": "@id" }}
+                }}
+            ],
+            "type": "https://www.w3.org/ns/activitystreams#Note",
+            "content": "Hello, world!",
+            "tag": [{}],
+            "signature": [{}]
+        }}"#,
+        tags, signatures,
+    )
+}
+
+fn options_dataset(signature: &Signature<'_>) -> LightDataset {
+    parse_nq(&format!(
+        "_:b0 <http://purl.org/dc/terms/created> \"{}\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n\
+         _:b0 <http://purl.org/dc/terms/creator> <{}> .\n\
+         _:b0 <https://w3id.org/security#nonce> \"{}\" .\n",
+        signature.created,
+        signature.creator,
+        signature.nonce.as_deref().unwrap(),
+    ))
+}
+
+fn bench(c: &mut Criterion) {
+    let key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+    let public_key = key.to_public_key();
+    let creator = Iri::new_unchecked(CREATOR);
+
+    for (name, n) in [("small", 0), ("medium", 100)] {
+        let dataset = parse_nq(&note_nq(n));
+        let signature = sign_rsa_signature_2017(&dataset, &key, creator).unwrap();
+        let options = options_dataset(&signature);
+        let json = note_json(n, std::slice::from_ref(&signature));
+        let signatures = (0..4)
+            .map(|_| sign_rsa_signature_2017(&dataset, &key, creator).unwrap())
+            .collect::<Vec<_>>();
+        let multi_document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &note_json(n, &signatures),
+            None,
+        ))
+        .unwrap();
+
+        c.bench_with_input(BenchmarkId::new("sign", name), &dataset, |b, dataset| {
+            b.iter(|| sign_rsa_signature_2017(dataset, &key, creator).unwrap())
+        });
+
+        c.bench_with_input(BenchmarkId::new("verify", name), &dataset, |b, dataset| {
+            b.iter(|| {
+                verify_rsa_signature_2017(
+                    dataset,
+                    &options,
+                    &public_key,
+                    &signature.signature_value,
+                )
+                .unwrap()
+            })
+        });
+
+        let mut verifier = <Verifier>::default();
+        c.bench_with_input(
+            BenchmarkId::new("verify_reused", name),
+            &dataset,
+            |b, dataset| {
+                b.iter(|| {
+                    verifier
+                        .verify(dataset, &options, &public_key, &signature.signature_value)
+                        .unwrap()
+                })
+            },
+        );
+
+        c.bench_with_input(BenchmarkId::new("json_ld", name), &json, |b, json| {
+            b.iter(|| {
+                let document =
+                    block_on(SignedDocument::<LightDataset>::parse_json_str(json, None)).unwrap();
+                document.verify_rsa_signature_2017(&public_key).unwrap();
+            })
+        });
+
+        c.bench_with_input(
+            BenchmarkId::new("json_ld_multi", name),
+            &multi_document,
+            |b, document| b.iter(|| document.verify_rsa_signature_2017(&public_key).unwrap()),
+        );
+
+        #[cfg(feature = "rayon")]
+        c.bench_with_input(
+            BenchmarkId::new("json_ld_multi_parallel", name),
+            &multi_document,
+            |b, document| b.iter(|| document.verify_all_parallel(&public_key).unwrap()),
+        );
+    }
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
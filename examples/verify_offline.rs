@@ -0,0 +1,74 @@
+//! Verifies an embedded signed document with an embedded public key, without network access.
+//!
+//! The document only refers to the Security Vocabulary context, which `PreloadedLoader` serves
+//! from memory, so this works without the `http` feature:
+//!
+//! ```shell
+//! cargo run --example verify_offline --no-default-features --features json-ld,std
+//! ```
+
+use std::sync::Arc;
+
+use futures_executor::block_on;
+use json_ld::RemoteDocument;
+use json_syntax::Parse as _;
+use rsa::pkcs8::DecodePublicKey as _;
+use rsa::RsaPublicKey;
+use rsa_signature_2017::json_ld::loader::PreloadedLoader;
+use rsa_signature_2017::json_ld::{DocumentParser, JsonLdOptions, SignedDocument};
+use sophia_inmem::dataset::LightDataset;
+use sophia_iri::Iri;
+
+const DOCUMENT: &str = r#"{
+    "@context": [
+        "https://w3id.org/security/v1",
+        {
+            "as": "https://www.w3.org/ns/activitystreams#",
+            "Note": "as:Note",
+            "content": "as:content"
+        }
+    ],
+    "id": "https://example.com/notes/1",
+    "type": "Note",
+    "content": "Hello, world!",
+    "signature": {
+        "@context": ["https://w3id.org/security/v1", { "@vocab": "sec:" }],
+        "type": "RsaSignature2017",
+        "created": "2024-01-01T00:00:00Z",
+        "creator": "https://example.com/users/alice#main-key",
+        "nonce": "0123456789abcdef",
+        "signatureValue": "x+nLxmt03WSHgnVYMWFp5kiRZpeTorKCr2UZWBwJvMzUuEQa+jiwjm07vV/J3dRSdyNIbNrT06yXdEW/f9Sr9sFPI5cNA1/mAgUZKUcvWkkpiN5eKF566WphPQZwRX0qCws6Kr6b9pfL0MwGnrc1tPG/OcJogfyW7dkmocR9zJlQm6T05ZatBurUzBynuMRJtXDkbBAm84vqSGsCoZTGKpWEtKqJvjJBRUe486pH9DuYF3xCWirp4mY09EOWMj09zwefMR1YSsxVoqSpjLWsgYT2DpXOgq/DxP2Hl4PSZdoqaK46WHR4sz6Z/YiL63GyrnwXdKs2H8hV2ymYpeXCtw=="
+    }
+}"#;
+
+/// The public key of `https://example.com/users/alice#main-key`.
+const PUBLIC_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA2xbL2h0Jogt4D6RxsDJs
+RkfcnXnJHZmHy/lrmGTK64R/M9o5vp5we0zogBGftI8Umy6aWeksO3jJlHUkiB3h
+PaIyVCMLO/o/GWOmUmxc/AyNf8SZ+LUErz/V585xWN9JTkqmzh4TVUj7esnRsilw
+/X98SF2iXYObOGaVzzgVxcTpKQpzl0qECZ/5EInneivBTJx8i8fLaOMQJaxXJ0p2
+FgrvKEx0YOEuvW14lIx5kbqyQnbBGNUg3TyMacbwBVZRxB6fpjnhjfxpljrBbyhW
+Hj64bAXM/79JQwTCcsq75iobdzuxmY81Thc3X0kzaKj1NJ8lViC2xEphe6rd5uJj
+iQIDAQAB
+-----END PUBLIC KEY-----";
+
+fn main() {
+    let key = RsaPublicKey::from_public_key_pem(PUBLIC_KEY).unwrap();
+
+    // Any remote context other than the preloaded ones fails to load instead of being fetched.
+    let parser = DocumentParser::with_options(
+        JsonLdOptions::new().with_document_loader(PreloadedLoader::new()),
+    );
+
+    let url = Iri::new_unchecked(Arc::from("https://example.com/notes/1"));
+    let json =
+        json_syntax::Value::parse_str(DOCUMENT, |span| locspan::Location::new(url.clone(), span))
+            .unwrap();
+    let document: SignedDocument<LightDataset> =
+        block_on(parser.parse(RemoteDocument::new(Some(url), None, json))).unwrap();
+
+    document.verify_rsa_signature_2017(&key).unwrap();
+    for creator in document.creators() {
+        println!("Verified the signature by {}", creator.unwrap().as_str());
+    }
+}
@@ -0,0 +1,31 @@
+//! Feeds arbitrary inputs to `SignedDocument::parse` to check that it never panics.
+//!
+//! Run with `cargo fuzz run json_ld_parse` from the repository root. The `corpus/json_ld_parse`
+//! directory contains the seed inputs.
+
+#![no_main]
+
+use futures_executor::block_on;
+use libfuzzer_sys::fuzz_target;
+use rsa_signature_2017::json_ld::SignedDocument;
+use sophia_inmem::dataset::LightDataset;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(document) = block_on(SignedDocument::<LightDataset>::parse_json_str(json, None)) else {
+        return;
+    };
+
+    // Exercise the accessors of the parsed signatures as well.
+    for signature in document.signatures() {
+        let _ = signature.created();
+        let _ = signature.creator();
+        let _ = signature.domain();
+        let _ = signature.nonce();
+        for quad in signature.other_options() {
+            let _ = quad;
+        }
+    }
+});
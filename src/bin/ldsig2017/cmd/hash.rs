@@ -0,0 +1,121 @@
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{stdin, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use clap::builder::{StringValueParser, TypedValueParser};
+use json_syntax::Parse as _;
+use rsa_signature_2017::json_ld::loader::{CachingLoader, HttpLoader, PreloadedLoader};
+use rsa_signature_2017::{canonicalize_document, GraphScope, Signature};
+use sophia_api::dataset::CollectibleDataset;
+use sophia_inmem::dataset::LightDataset;
+use sophia_iri::Iri;
+use sophia_jsonld::loader::ChainLoader;
+use sophia_jsonld::vocabulary::ArcIri;
+use sophia_jsonld::{JsonLdOptions, JsonLdParser};
+
+use super::sign::{parse_rdf, InputFormat};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// URI of the key pair
+    #[arg(short, long, value_name = "URI", value_hint = clap::ValueHint::Url)]
+    #[arg(value_parser = StringValueParser::new().try_map(Iri::new))]
+    creator: Iri<String>,
+    /// The date and time of the signature generation in the ISO 8601 format.
+    #[arg(long, value_name = "DATETIME", value_hint = clap::ValueHint::Other)]
+    created: String,
+    /// Nonce value of the signature [default: none]
+    #[arg(long)]
+    nonce: Option<String>,
+    /// Domain of the signature [default: none]
+    #[arg(long)]
+    domain: Option<String>,
+    /// Format of the input documents
+    #[arg(long, value_name = "FORMAT", default_value_t = Default::default())]
+    input_format: InputFormat,
+    /// Print the canonical N-Quads of the signature options and the documents that get hashed,
+    /// instead of the hashes
+    #[arg(long)]
+    canonical: bool,
+    /// Documents to hash
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    input: Vec<PathBuf>,
+}
+
+pub async fn main(args: Args) -> anyhow::Result<()> {
+    let placeholder_iri = Iri::new_unchecked(Arc::from("urn:x-placeholder"));
+
+    let paths = if args.input.is_empty() {
+        vec![Path::new("-")]
+    } else {
+        args.input.iter().map(PathBuf::as_path).collect()
+    };
+
+    let mut sign_options = Signature::options();
+    sign_options
+        .created(&*args.created)
+        .nonce(Some(args.nonce.as_deref()))
+        .domain(args.domain.as_deref());
+
+    // Share the fetched contexts among the inputs.
+    let json_ld_options = JsonLdOptions::new().with_document_loader(ChainLoader::new(
+        PreloadedLoader::new(),
+        CachingLoader::new(HttpLoader::new()),
+    ));
+    let json_ld_parser = JsonLdParser::new_with_options(json_ld_options);
+
+    for path in paths {
+        let mut json = String::new();
+        if path == OsStr::new("-") {
+            stdin().lock().read_to_string(&mut json)
+        } else {
+            let input =
+                File::open(path).with_context(|| format!("unable to open input: {:?}", path))?;
+            BufReader::new(input).read_to_string(&mut json)
+        }
+        .with_context(|| format!("unable to read input: {:?}", path))?;
+
+        let dataset = if let Some(dataset) = parse_rdf(args.input_format, &json)
+            .with_context(|| format!("unable to parse input: {:?}", path))?
+        {
+            dataset
+        } else {
+            let path_iri: ArcIri = Iri::new(format!("file://{:?}", path).into())
+                .unwrap_or_else(|_| placeholder_iri.clone());
+            let mut json = json_syntax::Value::parse_str(&json, |span| {
+                locspan::Location::new(path_iri.clone(), span)
+            })
+            .with_context(|| format!("unable to parse input: {:?}", path))?;
+
+            let Some(object) = json.as_object_mut() else {
+                anyhow::bail!("{:?}: expected JSON object, got {}", path, json.kind());
+            };
+            // An existing signature is not covered by the hash.
+            object.remove("signature");
+
+            let document = json_ld::RemoteDocument::new(None, None, json);
+            let quads = json_ld_parser.parse_json(&document).await;
+            LightDataset::from_quad_source(quads)?
+        };
+
+        if args.canonical {
+            let options = sign_options
+                .canonicalize_options(args.creator.as_ref())
+                .with_context(|| format!("unable to canonicalize input: {:?}", path))?;
+            let document = canonicalize_document(&dataset, GraphScope::All)
+                .with_context(|| format!("unable to canonicalize input: {:?}", path))?;
+            print!("# signature options\n{}# document\n{}", options, document);
+            continue;
+        }
+
+        let hash = sign_options
+            .create_verify_hash_rsa_signature_2017(&dataset, args.creator.as_ref())
+            .with_context(|| format!("unable to hash input: {:?}", path))?;
+        println!("{}", hex::encode(hash));
+    }
+
+    Ok(())
+}
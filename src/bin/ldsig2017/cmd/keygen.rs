@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use rsa::RsaPrivateKey;
+
+use crate::common::{write_rsa_private_key_file, write_rsa_public_key_file, KeyFormat};
+
+/// Key sizes below this value are considered insecure.
+const MIN_SECURE_BITS: usize = 2048;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Size of the key in bits
+    #[arg(long, value_name = "BITS", default_value_t = MIN_SECURE_BITS)]
+    bits: usize,
+    /// Format of the keys
+    #[arg(long, value_name = "FORMAT", default_value_t = Default::default())]
+    key_format: KeyFormat,
+    /// Path to write the private key to
+    #[arg(short, long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    out: PathBuf,
+    /// Path to write the public key to
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    pub_out: Option<PathBuf>,
+}
+
+pub fn main(args: Args) -> anyhow::Result<()> {
+    if args.bits < MIN_SECURE_BITS {
+        eprintln!(
+            "warning: {}-bit RSA keys are insecure; use at least {} bits",
+            args.bits, MIN_SECURE_BITS
+        );
+    }
+
+    let key = RsaPrivateKey::new(&mut rand::thread_rng(), args.bits)
+        .map_err(|e| anyhow::anyhow!("unable to generate private key: {}", e))?;
+
+    write_rsa_private_key_file(args.key_format, &args.out, &key)?;
+    if let Some(ref pub_out) = args.pub_out {
+        write_rsa_public_key_file(args.key_format, pub_out, key.as_ref())?;
+    }
+
+    Ok(())
+}
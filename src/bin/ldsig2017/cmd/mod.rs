@@ -1 +1,3 @@
+pub mod hash;
+pub mod keygen;
 pub mod sign;
@@ -1,23 +1,34 @@
+use core::fmt::{self, Display, Formatter};
+use std::env;
 use std::ffi::OsStr;
 use std::fs::File;
-use std::io::{stdin, BufReader, Read};
+use std::io::{stdin, stdout, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Context as _;
 use clap::builder::{StringValueParser, TypedValueParser};
-use json_ld::ReqwestLoader;
 use json_syntax::Parse as _;
-use rsa_signature_2017::json_ld::loader::PreloadedLoader;
-use rsa_signature_2017::Signature;
+use rsa_signature_2017::json_ld::loader::{CachingLoader, HttpLoader, PreloadedLoader};
+use rsa_signature_2017::json_ld::{remove_signature_entries, DocumentParser};
+use rsa_signature_2017::{
+    rsa_public_key_fingerprint, FingerprintEncoding, Signature, SignatureEncoding,
+};
 use sophia_api::dataset::CollectibleDataset;
+use sophia_api::parser::{QuadParser, TripleParser};
+use sophia_api::source::TripleSource;
 use sophia_inmem::dataset::LightDataset;
 use sophia_iri::Iri;
 use sophia_jsonld::loader::ChainLoader;
 use sophia_jsonld::vocabulary::ArcIri;
 use sophia_jsonld::{JsonLdOptions, JsonLdParser};
+use sophia_turtle::parser::nq::NQuadsParser;
+use sophia_turtle::parser::turtle::TurtleParser;
 
-use crate::common::{read_rsa_private_key_file, KeyFormat};
+use crate::common::{decode_rsa_private_key, read_rsa_private_key_file, KeyFormat};
+
+/// Environment variable to read the PEM-encoded private key from when `--key` is omitted.
+const KEY_ENV: &str = "LDSIG2017_KEY";
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -25,43 +36,165 @@ pub struct Args {
     #[arg(short, long, value_name = "URI", value_hint = clap::ValueHint::Url)]
     #[arg(value_parser = StringValueParser::new().try_map(Iri::new))]
     creator: Iri<String>,
-    /// Private key to sign the documents with
+    /// Private key to sign the documents with, or `-` to read it from stdin [default: PEM-encoded
+    /// key in the `LDSIG2017_KEY` environment variable]
     #[arg(short, long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
-    key: PathBuf,
+    key: Option<PathBuf>,
     /// The date and time of the signature generation in the ISO 8601 format.
     #[arg(long, value_name = "DATETIME", value_hint = clap::ValueHint::Other)]
     created: Option<String>,
     /// Format of the private key
     #[arg(long, value_name = "FORMAT", default_value_t = Default::default())]
     key_format: KeyFormat,
+    /// Nonce value of the signature, or an empty string to omit it [default: random]
     #[arg(long)]
     nonce: Option<String>,
+    /// Verify each signature after signing and abort if it doesn't verify (JSON-LD input only)
+    #[arg(long)]
+    verify: bool,
+    /// Replace an existing `signature` entry of the input documents, which is the default
+    #[arg(long, overrides_with = "no_replace")]
+    replace: bool,
+    /// Fail instead of replacing an existing `signature` entry of the input documents
+    #[arg(long, overrides_with = "replace")]
+    no_replace: bool,
+    /// Encoding of the signature value
+    #[arg(long, value_name = "ENCODING", default_value_t = Default::default())]
+    signature_encoding: Encoding,
+    /// Format of the input documents. Signatures of non-JSON-LD documents are written as
+    /// standalone JSON objects
+    #[arg(long, value_name = "FORMAT", default_value_t = Default::default())]
+    input_format: InputFormat,
+    /// File to write the signed documents to [default: stdout]
+    #[arg(short, long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    output: Option<PathBuf>,
     /// Documents to sign
     #[arg(value_hint = clap::ValueHint::FilePath)]
     input: Vec<PathBuf>,
 }
 
+#[derive(Clone, Copy, Default, clap::ValueEnum)]
+pub enum InputFormat {
+    /// JSON-LD
+    #[default]
+    JsonLd,
+    /// N-Quads
+    Nquads,
+    /// Turtle
+    Turtle,
+}
+
+impl InputFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InputFormat::JsonLd => "json-ld",
+            InputFormat::Nquads => "nquads",
+            InputFormat::Turtle => "turtle",
+        }
+    }
+}
+
+impl Display for InputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Default, clap::ValueEnum)]
+pub enum Encoding {
+    /// Standard base64 with padding
+    #[default]
+    Base64,
+    /// URL-safe base64 without padding
+    Base64url,
+}
+
+impl Encoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Base64 => "base64",
+            Encoding::Base64url => "base64url",
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Encoding> for SignatureEncoding {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Base64 => SignatureEncoding::Base64Standard,
+            Encoding::Base64url => SignatureEncoding::Base64Url,
+        }
+    }
+}
+
 pub async fn main(args: Args) -> anyhow::Result<()> {
-    let key = read_rsa_private_key_file(args.key_format, &args.key)?;
+    if args.verify && !matches!(args.input_format, InputFormat::JsonLd) {
+        anyhow::bail!("`--verify` is only supported for JSON-LD input");
+    }
+
+    let key = if let Some(ref path) = args.key {
+        if path == OsStr::new("-")
+            && (args.input.is_empty() || args.input.iter().any(|input| input == OsStr::new("-")))
+        {
+            anyhow::bail!("cannot read both the private key and an input from stdin");
+        }
+        read_rsa_private_key_file(args.key_format, path)?
+    } else if let Some(key) = env::var_os(KEY_ENV) {
+        let key = key
+            .into_string()
+            .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8", KEY_ENV))?;
+        decode_rsa_private_key(args.key_format, key.as_bytes())?
+    } else {
+        anyhow::bail!("no private key given; specify `--key` or set {}", KEY_ENV);
+    };
     let creator = args.creator.as_ref();
 
     let placeholder_iri = Iri::new_unchecked(Arc::from("urn:x-placeholder"));
 
-    let mut inputs = args.input.iter();
-    let mut path = if let Some(input) = inputs.next() {
-        input
+    let paths = if args.input.is_empty() {
+        vec![Path::new("-")]
     } else {
-        Path::new("-")
+        args.input.iter().map(PathBuf::as_path).collect()
     };
 
     let mut sign_options = Signature::options();
-    sign_options.created(args.created.as_deref()).nonce(
-        args.nonce
-            .as_ref()
-            .map(|nonce| (!nonce.is_empty()).then_some(nonce.as_str())),
+    sign_options
+        .created(args.created.as_deref())
+        .signature_encoding(args.signature_encoding.into());
+    match args.nonce.as_deref() {
+        // An empty `--nonce` omits the nonce.
+        Some("") => sign_options.no_nonce(),
+        Some(nonce) => sign_options.nonce(Some(Some(nonce))),
+        None => sign_options.auto_nonce(),
+    };
+
+    let replace = args.replace || !args.no_replace;
+
+    // Share the fetched contexts among the inputs and the verification of their signatures.
+    let loader = ChainLoader::new(
+        PreloadedLoader::new(),
+        CachingLoader::new(HttpLoader::new()),
     );
+    let json_ld_parser =
+        JsonLdParser::new_with_options(JsonLdOptions::new().with_document_loader(loader.clone()));
+    let verify_parser =
+        DocumentParser::with_options(JsonLdOptions::new().with_document_loader(loader));
+
+    let mut output: Box<dyn Write> = if let Some(ref path) = args.output {
+        let file =
+            File::create(path).with_context(|| format!("unable to create output: {:?}", path))?;
+        Box::new(BufWriter::new(file))
+    } else {
+        Box::new(BufWriter::new(stdout().lock()))
+    };
 
-    loop {
+    for path in paths {
         let mut json = String::new();
         if path == OsStr::new("-") {
             stdin().lock().read_to_string(&mut json)
@@ -72,52 +205,99 @@ pub async fn main(args: Args) -> anyhow::Result<()> {
         }
         .with_context(|| format!("unable to read input: {:?}", path))?;
 
+        if let Some(dataset) = parse_rdf(args.input_format, &json)
+            .with_context(|| format!("unable to parse input: {:?}", path))?
+        {
+            let signature = sign_options
+                .sign_rsa_signature_2017(&dataset, &key, creator)
+                .with_context(|| format!("unable to sign input: {:?}", path))?;
+            // There is no document to embed the signature into, so emit the signature alone.
+            let signature_json = json_syntax::to_value_with(&signature, || {
+                locspan::Location::new(placeholder_iri.clone(), locspan::Span::default())
+            })
+            .unwrap();
+            writeln!(output, "{}", signature_json).context("unable to write output")?;
+            continue;
+        }
+
         let path_iri: ArcIri = Iri::new(format!("file://{:?}", path).into())
             .unwrap_or_else(|_| placeholder_iri.clone());
-        let json = json_syntax::Value::parse_str(&json, |span| {
+        let mut json = json_syntax::Value::parse_str(&json, |span| {
             locspan::Location::new(path_iri.clone(), span)
         })
         .with_context(|| format!("unable to parse inout: {:?}", path))?;
 
-        if !json.is_object() {
+        let Some(object) = json.as_object() else {
             anyhow::bail!("{:?}: expected JSON object, got {}", path, json.kind());
+        };
+        if !replace && object.get("signature").next().is_some() {
+            anyhow::bail!(
+                "{:?}: the document already has a signature; remove it or pass `--replace`",
+                path
+            );
         }
+        // The existing signature must not be signed along with the rest of the document.
+        remove_signature_entries(&mut json);
 
         let document = json_ld::RemoteDocument::new(None, None, json);
 
-        let json_ld_options = JsonLdOptions::new()
-            .with_default_document_loader::<ChainLoader<PreloadedLoader, ReqwestLoader<ArcIri>>>();
-        let quads = JsonLdParser::new_with_options(json_ld_options)
-            .parse_json(&document)
-            .await;
+        let quads = json_ld_parser.parse_json(&document).await;
         let dataset = LightDataset::from_quad_source(quads)?;
 
         let signature = sign_options
             .sign_rsa_signature_2017(&dataset, &key, creator)
             .with_context(|| format!("unable to sign input: {:?}", path))?;
 
-        let signature_key = locspan::Meta::new(
-            "signature".into(),
-            locspan::Location::new(placeholder_iri.clone(), Default::default()),
-        );
-        let signature_json = json_syntax::to_value_with(&signature, || {
-            locspan::Location::new(placeholder_iri.clone(), Default::default())
-        })
-        .unwrap();
-
-        let mut json = document.into_document();
-        json.as_object_mut()
-            .unwrap()
-            .insert(signature_key, signature_json);
+        let json = document.into_document();
 
-        println!("{}", json);
+        if args.verify {
+            let signature_key = locspan::Meta::new(
+                "signature".into(),
+                locspan::Location::new(placeholder_iri.clone(), Default::default()),
+            );
+            let signature_json = json_syntax::to_value_with(&signature, || {
+                locspan::Location::new(placeholder_iri.clone(), Default::default())
+            })
+            .unwrap();
+            let mut json = json.clone();
+            json.as_object_mut()
+                .unwrap()
+                .insert(signature_key, signature_json);
 
-        if let Some(input) = inputs.next() {
-            path = input;
-        } else {
-            break;
+            let signed = verify_parser
+                .parse::<LightDataset, LightDataset>(json_ld::RemoteDocument::new(None, None, json))
+                .await
+                .map_err(|e| anyhow::anyhow!("{:?}", e))
+                .with_context(|| format!("unable to parse the signed output of {:?}", path))?;
+            signed
+                .verify_rsa_signature_2017(key.as_ref())
+                .with_context(|| format!("the signature of {:?} didn't verify", path))?;
+            eprintln!(
+                "{:?}: verified with key {}",
+                path,
+                rsa_public_key_fingerprint(&key.to_public_key(), FingerprintEncoding::Hex),
+            );
         }
+
+        signature
+            .write_signed_document_to(&json, &mut output)
+            .and_then(|()| writeln!(output))
+            .context("unable to write output")?;
     }
 
+    output.flush().context("unable to write output")?;
+
     Ok(())
 }
+
+/// Parses the `input` as a non-JSON-LD RDF document, or returns `None` for JSON-LD input.
+pub fn parse_rdf(format: InputFormat, input: &str) -> anyhow::Result<Option<LightDataset>> {
+    let dataset = match format {
+        InputFormat::JsonLd => return Ok(None),
+        InputFormat::Nquads => LightDataset::from_quad_source(NQuadsParser {}.parse_str(input))?,
+        InputFormat::Turtle => {
+            LightDataset::from_quad_source(TurtleParser { base: None }.parse_str(input).to_quads())?
+        }
+    };
+    Ok(Some(dataset))
+}
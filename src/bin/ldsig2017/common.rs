@@ -1,3 +1,6 @@
 mod crypto;
 
-pub use self::crypto::{read_rsa_private_key_file, KeyFormat};
+pub use self::crypto::{
+    decode_rsa_private_key, read_rsa_private_key_file, write_rsa_private_key_file,
+    write_rsa_public_key_file, KeyFormat,
+};
@@ -16,7 +16,11 @@ struct Cli {
 
 #[derive(clap::Subcommand)]
 enum Commands {
-    /// Signs a JSON-LD document
+    /// Prints the hex-encoded hash that would be signed for a document, without signing it
+    Hash(cmd::hash::Args),
+    /// Generates an RSA key pair
+    Keygen(cmd::keygen::Args),
+    /// Signs a JSON-LD, N-Quads or Turtle document
     Sign(cmd::sign::Args),
 }
 
@@ -24,6 +28,8 @@ enum Commands {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     match cli.command {
+        Commands::Hash(args) => cmd::hash::main(args).await,
+        Commands::Keygen(args) => cmd::keygen::main(args),
         Commands::Sign(args) => cmd::sign::main(args).await,
     }
 }
@@ -1,37 +1,139 @@
 pub mod consts;
 
-use std::io::Write;
+use std::borrow::Cow;
 
 use either::Either;
+#[cfg(any(test, feature = "sign", feature = "verify"))]
 use sha2::digest::Output;
+use sha2::digest::{FixedOutputReset, Update};
 use sha2::{Digest, Sha256};
-use sophia_api::dataset::{MutableDataset, SetDataset};
+use sophia_api::dataset::{DQuadSource, Dataset, MutableDataset, SetDataset};
+#[cfg(feature = "sign")]
+use sophia_api::quad::Quad;
+use sophia_api::term::matcher::Any;
 use sophia_api::term::{BnodeId, SimpleTerm};
-use sophia_api::MownStr;
+#[cfg(feature = "sign")]
+use sophia_api::term::{Term, TermKind};
 use sophia_c14n::rdfc10;
 use sophia_inmem::dataset::LightDataset;
 use sophia_iri::{Iri, IriRef};
 
 use crate::error::DatasetError;
+use crate::sign::{CreatorKey, Signature};
 use crate::util::DigestWrite;
 
+/// The graphs of a dataset to be covered by a signature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GraphScope {
+    /// Only the default graph.
+    ///
+    /// This is how the Linked Data Signatures algorithm is applied to ActivityPub documents, whose
+    /// named graphs (e.g., from `@graph` nesting) may be ignored by other implementations.
+    Default,
+    /// All the graphs of the dataset, including the named graphs.
+    ///
+    /// The same triple in different graphs counts as distinct quads, each of which is covered by
+    /// the signature, as in the RDF Dataset Canonicalization.
+    #[default]
+    All,
+}
+
+/// The signature options of a Linked Data Signature, i.e., the properties of the signature node
+/// other than its type and value, which are hashed along with the document.
+///
+/// A [`Signature`] converts into its options with `From`, whose
+/// [dataset](SignatureOptions::to_dataset) can be passed to the dataset-level verification APIs.
+#[non_exhaustive]
 pub struct SignatureOptions<'a> {
     pub created: &'a str,
+    pub expires: Option<&'a str>,
     pub creator: Iri<&'a str>,
+    pub creator_key: CreatorKey,
     pub domain: Option<&'a str>,
     pub nonce: Option<&'a str>,
+    pub extra_options: &'a [(Iri<Cow<'a, str>>, SimpleTerm<'a>)],
 }
 
 impl<'a> SignatureOptions<'a> {
+    /// Creates the options with the required `created` datetime and `creator` IRI, and no other
+    /// properties.
+    ///
+    /// The struct is `#[non_exhaustive]`, so the other properties are set with the `with_*`
+    /// methods:
+    ///
+    /// ```
+    /// use rsa_signature_2017::SignatureOptions;
+    /// use sophia_api::dataset::Dataset;
+    /// use sophia_iri::Iri;
+    ///
+    /// let options = SignatureOptions::new(
+    ///     "2024-01-01T00:00:00Z",
+    ///     Iri::new("https://example.com/#me").unwrap(),
+    /// )
+    /// .with_nonce("deadbeef12345678")
+    /// .with_domain("example.com");
+    /// assert_eq!(options.nonce, Some("deadbeef12345678"));
+    /// assert_eq!(options.to_dataset().quads().count(), 4);
+    /// ```
+    pub fn new(created: &'a str, creator: Iri<&'a str>) -> Self {
+        SignatureOptions {
+            created,
+            expires: None,
+            creator,
+            creator_key: CreatorKey::Creator,
+            domain: None,
+            nonce: None,
+            extra_options: &[],
+        }
+    }
+
+    pub fn with_expires(mut self, expires: impl Into<Option<&'a str>>) -> Self {
+        self.expires = expires.into();
+        self
+    }
+
+    pub fn with_creator_key(mut self, creator_key: CreatorKey) -> Self {
+        self.creator_key = creator_key;
+        self
+    }
+
+    pub fn with_domain(mut self, domain: impl Into<Option<&'a str>>) -> Self {
+        self.domain = domain.into();
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: impl Into<Option<&'a str>>) -> Self {
+        self.nonce = nonce.into();
+        self
+    }
+
+    pub fn with_extra_options(
+        mut self,
+        extra_options: &'a [(Iri<Cow<'a, str>>, SimpleTerm<'a>)],
+    ) -> Self {
+        self.extra_options = extra_options;
+        self
+    }
+
+    /// Converts the options to a dataset.
     pub fn to_dataset(&self) -> LightDataset {
-        const ID: MownStr<'_> = MownStr::from_str("b0");
+        self.to_dataset_with_bnode(None)
+    }
+
+    /// Converts the options to a dataset, labeling the options node with `bnode_label`, which
+    /// defaults to `b0`.
+    ///
+    /// The label doesn't affect the hash since the canonicalization relabels blank nodes anyway.
+    pub fn to_dataset_with_bnode(&self, bnode_label: Option<BnodeId<&str>>) -> LightDataset {
+        let id = bnode_label.unwrap_or(BnodeId::new_unchecked("b0"));
 
         let mut ret = LightDataset::new();
 
         // Unwrapping is fine because `LightDataset::insert` returns error only when too many terms
         // are inserted.
         ret.insert(
-            BnodeId::new_unchecked(ID),
+            id,
             IriRef::new_unchecked(consts::CREATED),
             SimpleTerm::LiteralDatatype(
                 self.created.into(),
@@ -40,16 +142,32 @@ impl<'a> SignatureOptions<'a> {
             None::<&'static SimpleTerm<'_>>,
         )
         .unwrap();
+        if let Some(expires) = self.expires {
+            ret.insert(
+                id,
+                IriRef::new_unchecked(consts::EXPIRATION),
+                SimpleTerm::LiteralDatatype(
+                    expires.into(),
+                    IriRef::new_unchecked(consts::DATETIME),
+                ),
+                None::<&'static SimpleTerm<'_>>,
+            )
+            .unwrap();
+        }
+        let creator_key = match self.creator_key {
+            CreatorKey::Creator => consts::CREATOR,
+            CreatorKey::VerificationMethod => consts::VERIFICATION_METHOD,
+        };
         ret.insert(
-            BnodeId::new_unchecked(ID),
-            IriRef::new_unchecked(consts::CREATOR),
+            id,
+            IriRef::new_unchecked(creator_key),
             self.creator,
             None::<&'static SimpleTerm<'_>>,
         )
         .unwrap();
         if let Some(domain) = self.domain {
             ret.insert(
-                BnodeId::new_unchecked(ID),
+                id,
                 IriRef::new_unchecked(consts::DOMAIN),
                 domain,
                 None::<&'static SimpleTerm<'_>>,
@@ -58,91 +176,390 @@ impl<'a> SignatureOptions<'a> {
         }
         if let Some(nonce) = self.nonce {
             ret.insert(
-                BnodeId::new_unchecked(ID),
+                id,
                 IriRef::new_unchecked(consts::NONCE),
                 nonce,
                 None::<&'static SimpleTerm<'_>>,
             )
             .unwrap();
         }
+        for (predicate, object) in self.extra_options {
+            ret.insert(
+                id,
+                predicate.as_ref(),
+                object,
+                None::<&'static SimpleTerm<'_>>,
+            )
+            .unwrap();
+        }
 
         ret
     }
 }
 
-/// Performs the Create Verify Hash Algorithm of the spec and returns its output.
+impl<'a> From<&'a Signature<'_>> for SignatureOptions<'a> {
+    fn from(signature: &'a Signature<'_>) -> Self {
+        SignatureOptions::new(&signature.created, signature.creator.as_ref())
+            .with_expires(signature.expires.as_deref())
+            .with_creator_key(signature.creator_key)
+            .with_domain(signature.domain.as_deref())
+            .with_nonce(signature.nonce.as_deref())
+            .with_extra_options(&signature.extra_options)
+    }
+}
+
+/// Performs the Create Verify Hash Algorithm of the spec with the digest algorithm `H` and returns
+/// its output.
+///
+/// Only the graphs of the `dataset` in the `scope` are canonicalized.
 #[allow(clippy::type_complexity)]
-pub fn create_verify_hash<D, O>(
+#[cfg(any(test, feature = "sign"))]
+pub fn create_verify_hash<H, D, O>(
     dataset: &D,
     options: &O,
-) -> Result<Output<Sha256>, Either<DatasetError<D::Error>, DatasetError<O::Error>>>
+    scope: GraphScope,
+) -> Result<Output<H>, Either<DatasetError<D::Error>, DatasetError<O::Error>>>
 where
+    H: Digest + Update + FixedOutputReset,
     D: SetDataset,
     O: SetDataset,
 {
-    // A hasher for the `output` variable of the Algorithm's spec.
-    let mut to_be_signed = Sha256::default();
-    // A hasher to be haphazardly reused throughout the method just to be stingy.
-    let mut hasher = Sha256::default();
-
-    // Separating to another function to reduce monomorphization bloat with `D`.
-    hash_canonicalized_options_document(options, &mut to_be_signed, &mut hasher)
-        .map_err(Either::Right)?;
-
-    fn hash_canonicalized_options_document<O>(
-        options: &O,
-        to_be_signed: &mut Sha256,
-        hasher: &mut Sha256,
-    ) -> Result<(), DatasetError<O::Error>>
+    create_verify_hash_with(dataset, options, scope, &mut H::new(), &mut Vec::new())
+}
+
+/// Like [`create_verify_hash`], but uses the given `hasher` and `scratch` buffer so that they can be
+/// reused across calls.
+///
+/// The `hasher` is reset after use.
+#[allow(clippy::type_complexity)]
+#[cfg(any(test, feature = "sign", feature = "verify"))]
+pub fn create_verify_hash_with<H, D, O>(
+    dataset: &D,
+    options: &O,
+    scope: GraphScope,
+    hasher: &mut H,
+    scratch: &mut Vec<u8>,
+) -> Result<Output<H>, Either<DatasetError<D::Error>, DatasetError<O::Error>>>
+where
+    H: Digest + Update + FixedOutputReset,
+    D: SetDataset,
+    O: SetDataset,
+{
+    // The `hasher` may have been left dirty if a previous call failed in the middle.
+    Digest::reset(hasher);
+
+    let hex_len = 2 * <H as Digest>::output_size();
+    scratch.clear();
+    scratch.resize(2 * hex_len, 0);
+
+    hash_document_hex_into(dataset, scope, hasher, &mut scratch[hex_len..])
+        .map_err(Either::Left)?;
+    finish_verify_hash(options, hasher, scratch).map_err(Either::Right)
+}
+
+#[cfg(feature = "json-ld")]
+/// Like [`create_verify_hash_with`], but takes the hex-encoded hash of the document computed by
+/// [`hash_document_hex`] instead of the document itself, so that the hash can be shared across
+/// multiple signatures of the same document.
+pub fn create_verify_hash_prehashed<H, O>(
+    document_hex: &[u8],
+    options: &O,
+    hasher: &mut H,
+    scratch: &mut Vec<u8>,
+) -> Result<Output<H>, DatasetError<O::Error>>
+where
+    H: Digest + Update + FixedOutputReset,
+    O: SetDataset,
+{
+    Digest::reset(hasher);
+
+    let hex_len = 2 * <H as Digest>::output_size();
+    debug_assert_eq!(document_hex.len(), hex_len);
+    scratch.clear();
+    scratch.resize(hex_len, 0);
+    scratch.extend_from_slice(document_hex);
+
+    finish_verify_hash(options, hasher, scratch)
+}
+
+#[cfg(feature = "json-ld")]
+/// Returns the hex-encoded digest of the graphs of the `dataset` in the `scope`, to be passed to
+/// [`create_verify_hash_prehashed`].
+pub fn hash_document_hex<H, D>(
+    dataset: &D,
+    scope: GraphScope,
+) -> Result<Vec<u8>, DatasetError<D::Error>>
+where
+    H: Digest + Update + FixedOutputReset,
+    D: SetDataset,
+{
+    let mut hex = vec![0; 2 * <H as Digest>::output_size()];
+    hash_document_hex_into(dataset, scope, &mut H::new(), &mut hex)?;
+    Ok(hex)
+}
+
+fn hash_document_hex_into<H, D>(
+    dataset: &D,
+    scope: GraphScope,
+    hasher: &mut H,
+    out: &mut [u8],
+) -> Result<(), DatasetError<D::Error>>
+where
+    H: Digest + Update + FixedOutputReset,
+    D: SetDataset,
+{
+    trace_event!(trace, "canonicalizing the document");
+    match scope {
+        GraphScope::Default => hash_dataset_hex_into(&DefaultGraph(dataset), hasher, out),
+        GraphScope::All => hash_dataset_hex_into(dataset, hasher, out),
+    }?;
+    trace_event!(
+        debug,
+        document_hash = core::str::from_utf8(out).unwrap(),
+        "canonicalized the document",
+    );
+    Ok(())
+}
+
+/// Hashes the `options` into the first half of the `scratch` buffer, whose second half holds the
+/// hex-encoded hash of the document, and returns the hash of the whole buffer.
+#[cfg(any(test, feature = "sign", feature = "verify"))]
+fn finish_verify_hash<H, O>(
+    options: &O,
+    hasher: &mut H,
+    scratch: &mut [u8],
+) -> Result<Output<H>, DatasetError<O::Error>>
+where
+    H: Digest + Update + FixedOutputReset,
+    O: SetDataset,
+{
+    fill_options_hex(options, hasher, scratch)?;
+
+    // The `output` variable of the Algorithm's spec.
+    Update::update(hasher, scratch);
+    let output = Digest::finalize_reset(hasher);
+    trace_event!(debug, verify_hash = %hex::encode(&output), "computed the verify hash");
+
+    Ok(output)
+}
+
+/// Hashes the `options` into the first half of the `scratch` buffer.
+///
+/// The `hasher` is reset after use.
+fn fill_options_hex<H, O>(
+    options: &O,
+    hasher: &mut H,
+    scratch: &mut [u8],
+) -> Result<(), DatasetError<O::Error>>
+where
+    H: Digest + Update + FixedOutputReset,
+    O: SetDataset,
+{
+    let (options_hex, _) = scratch.split_at_mut(2 * <H as Digest>::output_size());
+
+    trace_event!(trace, "canonicalizing the signature options");
+    hash_dataset_hex_into(options, hasher, options_hex)?;
+    trace_event!(
+        debug,
+        options_hash = core::str::from_utf8(options_hex).unwrap(),
+        "canonicalized the signature options",
+    );
+
+    Ok(())
+}
+
+/// Returns the message that the `RsaSignature2017` suite hashes last in the Create Verify Hash
+/// Algorithm, i.e., the hex-encoded SHA-256 digest of the canonicalized signature `options`
+/// followed by that of the `dataset`.
+///
+/// The output is the 128-character string that other implementations commonly log for debugging.
+/// Comparing it against theirs tells whether two implementations agree on the canonicalization
+/// before the signature algorithm comes into play.
+#[allow(clippy::type_complexity)]
+pub fn create_verify_hash_input<D, O>(
+    dataset: &D,
+    options: &O,
+) -> Result<String, Either<DatasetError<D::Error>, DatasetError<O::Error>>>
+where
+    D: SetDataset,
+    O: SetDataset,
+{
+    let mut hasher = Sha256::new();
+    let hex_len = 2 * <Sha256 as Digest>::output_size();
+    let mut scratch = vec![0; 2 * hex_len];
+
+    hash_document_hex_into(
+        dataset,
+        GraphScope::All,
+        &mut hasher,
+        &mut scratch[hex_len..],
+    )
+    .map_err(Either::Left)?;
+    fill_options_hex(options, &mut hasher, &mut scratch).map_err(Either::Right)?;
+    // `hex::encode_to_slice` only writes ASCII characters.
+    Ok(String::from_utf8(scratch).unwrap())
+}
+
+/// Returns the hex-encoded SHA-256 digest of the canonical N-Quads serialization of the `dataset`.
+///
+/// This is the hash that the `RsaSignature2017` suite computes for each of the document and the
+/// signature options in the Create Verify Hash Algorithm before hashing their concatenation.
+pub fn hash_dataset_hex<D: SetDataset>(dataset: &D) -> Result<String, DatasetError<D::Error>> {
+    let mut hasher = Sha256::new();
+    let mut hex = vec![0; 2 * <Sha256 as Digest>::output_size()];
+    hash_dataset_hex_into(dataset, &mut hasher, &mut hex)?;
+    // `hex::encode_to_slice` only writes ASCII characters.
+    Ok(String::from_utf8(hex).unwrap())
+}
+
+/// Returns the canonical N-Quads serialization of the graphs of the `dataset` in the `scope`, with
+/// the blank nodes labeled deterministically (`_:c14n0`, `_:c14n1`, ...) by the RDF Dataset
+/// Canonicalization algorithm.
+///
+/// This is the exact form of the document that the Create Verify Hash Algorithm hashes, which is
+/// handy for diffing against the output of other implementations.
+pub fn canonicalize_document<D: SetDataset>(
+    dataset: &D,
+    scope: GraphScope,
+) -> Result<String, DatasetError<D::Error>> {
+    match scope {
+        GraphScope::Default => canonicalize(&DefaultGraph(dataset)),
+        GraphScope::All => canonicalize(dataset),
+    }
+}
+
+/// Returns the canonical N-Quads serialization of the signature `options`.
+///
+/// Like [`canonicalize_document`], but for the signature options dataset, whose blank node is
+/// labeled `_:c14n0`.
+pub fn canonicalize_options<O: SetDataset>(options: &O) -> Result<String, DatasetError<O::Error>> {
+    canonicalize(options)
+}
+
+fn canonicalize<D: SetDataset>(dataset: &D) -> Result<String, DatasetError<D::Error>> {
+    let mut nquads = Vec::new();
+    rdfc10::normalize(dataset, &mut nquads).map_err(DatasetError::from_c14n_error)?;
+    // The N-Quads serializer only writes valid UTF-8.
+    Ok(String::from_utf8(nquads).unwrap())
+}
+
+/// Writes the hex-encoded digest of the canonical N-Quads serialization of the `dataset` to `out`,
+/// whose length must be twice the output size of `H`.
+///
+/// The `hasher` is reset after use.
+fn hash_dataset_hex_into<H, D>(
+    dataset: &D,
+    hasher: &mut H,
+    out: &mut [u8],
+) -> Result<(), DatasetError<D::Error>>
+where
+    H: Digest + Update + FixedOutputReset,
+    D: SetDataset,
+{
+    rdfc10::normalize(dataset, DigestWrite::new(hasher)).map_err(DatasetError::from_c14n_error)?;
+    hex::encode_to_slice(Digest::finalize_reset(hasher), out).unwrap();
+    Ok(())
+}
+
+/// A view of the default graph of a dataset as a dataset.
+struct DefaultGraph<'a, D>(&'a D);
+
+impl<D: Dataset> Dataset for DefaultGraph<'_, D> {
+    type Quad<'x>
+        = D::Quad<'x>
     where
-        O: SetDataset,
-    {
-        rdfc10::normalize(options, DigestWrite::new(hasher))
-            .map_err(DatasetError::from_c14n_error)?;
-        let output = hasher.finalize_reset();
-        write!(
-            DigestWrite::new(to_be_signed),
-            "{}",
-            hex::encode(output.as_slice())
+        Self: 'x;
+    type Error = D::Error;
+
+    fn quads(&self) -> DQuadSource<'_, Self> {
+        Box::new(
+            self.0
+                .quads_matching(Any, Any, Any, [None::<&'static SimpleTerm<'_>>]),
         )
-        .unwrap();
-        Ok(())
     }
+}
 
-    rdfc10::normalize(dataset, DigestWrite::new(&mut hasher))
-        .map_err(|e| Either::Left(DatasetError::from_c14n_error(e)))?;
+impl<D: SetDataset> SetDataset for DefaultGraph<'_, D> {}
 
-    return Ok(finalize(to_be_signed, hasher));
+/// Returns an error if the `dataset` has more than `limit` quads, without iterating over the whole
+/// dataset.
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub fn check_quad_count<D: Dataset>(
+    dataset: &D,
+    limit: usize,
+) -> Result<(), DatasetError<D::Error>> {
+    let mut quads = dataset.quads();
+    for _ in 0..=limit {
+        match quads.next() {
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(DatasetError::Dataset(e)),
+            None => return Ok(()),
+        }
+    }
+    Err(DatasetError::TooManyQuads { limit })
+}
 
-    fn finalize(mut to_be_signed: Sha256, document_hasher: Sha256) -> Output<Sha256> {
-        let mut digest = document_hasher.finalize();
-        write!(
-            DigestWrite::new(&mut to_be_signed),
-            "{}",
-            hex::encode(digest.as_slice())
-        )
-        .unwrap();
+/// Returns whether the `dataset` has no quads in the graphs of the `scope`.
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub fn is_empty_in_scope<D: Dataset>(dataset: &D, scope: GraphScope) -> Result<bool, D::Error> {
+    let first = match scope {
+        GraphScope::Default => DefaultGraph(dataset).quads().next().map(|q| q.map(drop)),
+        GraphScope::All => dataset.quads().next().map(|q| q.map(drop)),
+    };
+    first.transpose().map(|first| first.is_none())
+}
 
-        to_be_signed.finalize_into(&mut digest);
+/// Returns the lexical forms of the literals in the graphs of the `scope` of the `dataset` that
+/// contain control characters that RDFC 1.0 and URDNA2015 canonicalize differently.
+///
+/// RDFC 1.0 escapes `U+0008`, `U+0009` and `U+000C` as `\b`, `\t` and `\f` and the other control
+/// characters except `U+000A` and `U+000D` as `\uXXXX`, while URDNA2015 leaves them unescaped.
+/// Only `U+000A` and `U+000D` are escaped (as `\n` and `\r`) by both.
+#[cfg(feature = "sign")]
+pub fn find_control_char_literals<D: Dataset>(
+    dataset: &D,
+    scope: GraphScope,
+) -> Result<Vec<String>, D::Error> {
+    match scope {
+        GraphScope::Default => control_char_literals(&DefaultGraph(dataset)),
+        GraphScope::All => control_char_literals(dataset),
+    }
+}
 
-        digest
+#[cfg(feature = "sign")]
+fn control_char_literals<D: Dataset>(dataset: &D) -> Result<Vec<String>, D::Error> {
+    let mut ret = Vec::new();
+    for quad in dataset.quads_matching(Any, Any, TermKind::Literal, Any) {
+        let o = quad?.to_o();
+        ret.extend(
+            o.lexical_form()
+                .filter(|lex| {
+                    lex.chars()
+                        .any(|c| c.is_ascii_control() && !matches!(c, '\n' | '\r'))
+                })
+                .map(Into::into),
+        );
     }
+    Ok(ret)
 }
 
 #[cfg(test)]
 mod tests {
+    use sophia_api::quad::Quad;
+    use sophia_api::term::Term;
+
     use super::*;
 
     use crate::util::test::parse_nq;
 
     #[test]
     fn options_to_dataset() {
-        let options = SignatureOptions {
-            created: "2024-01-01T00:00:00Z",
-            creator: Iri::new("https://example.com/users/1#main-key").unwrap(),
-            domain: Some("https://w3id.org/security#assertionMethod"),
-            nonce: Some("deadbeef12345678"),
-        };
+        let options = SignatureOptions::new(
+            "2024-01-01T00:00:00Z",
+            Iri::new("https://example.com/users/1#main-key").unwrap(),
+        )
+        .with_domain("https://w3id.org/security#assertionMethod")
+        .with_nonce("deadbeef12345678");
 
         const EXPECTED: &str = r#"
             _:b0 <http://purl.org/dc/terms/created> "2024-01-01T00:00:00Z"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
@@ -155,13 +572,117 @@ mod tests {
     }
 
     #[test]
-    fn create_verify_hash() {
-        let options = SignatureOptions {
-            created: "2024-01-01T00:00:00Z",
-            creator: Iri::new("https://example.com/users/1#main-key").unwrap(),
-            domain: Some("https://w3id.org/security#assertionMethod"),
-            nonce: Some("deadbeef12345678"),
+    fn options_bnode_label() {
+        let options = SignatureOptions::new(
+            "2024-01-01T00:00:00Z",
+            Iri::new("https://example.com/users/1#main-key").unwrap(),
+        );
+
+        let dataset = options.to_dataset_with_bnode(Some(BnodeId::new_unchecked("c14n0")));
+        for quad in dataset.quads() {
+            assert_eq!(quad.unwrap().s().bnode_id().unwrap().as_str(), "c14n0");
+        }
+        assert_eq!(
+            super::hash_dataset_hex(&dataset).unwrap(),
+            super::hash_dataset_hex(&options.to_dataset()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn hash_dataset_hex() {
+        const DATASET: &str = r#"
+            _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let dataset = parse_nq(DATASET);
+        let mut nquads = Vec::new();
+        rdfc10::normalize(&dataset, &mut nquads).unwrap();
+        assert_eq!(
+            super::hash_dataset_hex(&dataset).unwrap(),
+            hex::encode(Sha256::digest(&nquads)),
+        );
+    }
+
+    #[test]
+    fn canonicalize() {
+        const DATASET: &str = r#"
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" <https://example.com/g> .
+        "#;
+
+        let dataset = parse_nq(DATASET);
+        let default = "_:c14n0 <https://www.w3.org/ns/activitystreams#content> \"Hello, world!\" .";
+        assert_eq!(
+            canonicalize_document(&dataset, GraphScope::Default).unwrap(),
+            format!("{}\n", default),
+        );
+
+        let nquads = canonicalize_document(&dataset, GraphScope::All).unwrap();
+        let mut lines: Vec<_> = nquads.lines().collect();
+        lines.sort_unstable();
+        assert_eq!(
+            lines,
+            [
+                default,
+                "_:c14n0 <https://www.w3.org/ns/activitystreams#content> \"Hello, world!\" <https://example.com/g> .",
+            ],
+        );
+        assert_eq!(
+            super::hash_dataset_hex(&dataset).unwrap(),
+            hex::encode(Sha256::digest(nquads)),
+        );
+    }
+
+    #[test]
+    fn multiple_graphs() {
+        const DATASET: &str = r#"
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" _:g0 .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" <https://example.com/g> .
+        "#;
+
+        let dataset = parse_nq(DATASET);
+        let mut nquads = Vec::new();
+        rdfc10::normalize(&dataset, &mut nquads).unwrap();
+        assert_eq!(
+            nquads
+                .split(|&b| b == b'\n')
+                .filter(|l| !l.is_empty())
+                .count(),
+            3
+        );
+
+        // Dropping the triple from any of the graphs changes the hash.
+        let hash = super::hash_dataset_hex(&dataset).unwrap();
+        for line in DATASET.lines().filter(|l| !l.trim().is_empty()) {
+            let partial = parse_nq(&DATASET.replace(line, ""));
+            assert_ne!(super::hash_dataset_hex(&partial).unwrap(), hash);
+        }
+
+        let options = LightDataset::new();
+        let hash = |dataset, scope| {
+            super::create_verify_hash::<Sha256, _, _>(dataset, &options, scope).unwrap()
         };
+        let default = parse_nq(DATASET.lines().nth(1).unwrap());
+        assert_eq!(
+            hash(&dataset, GraphScope::Default),
+            hash(&default, GraphScope::All),
+        );
+        assert_ne!(
+            hash(&dataset, GraphScope::All),
+            hash(&default, GraphScope::All),
+        );
+    }
+
+    #[test]
+    fn create_verify_hash() {
+        let options = SignatureOptions::new(
+            "2024-01-01T00:00:00Z",
+            Iri::new("https://example.com/users/1#main-key").unwrap(),
+        )
+        .with_domain("https://w3id.org/security#assertionMethod")
+        .with_nonce("deadbeef12345678");
 
         const DATASET: &str = r#"
             _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
@@ -170,10 +691,70 @@ mod tests {
 
         assert_eq!(
             hex::encode(
-                super::create_verify_hash(&parse_nq(DATASET), &options.to_dataset()).unwrap()
+                super::create_verify_hash::<Sha256, _, _>(
+                    &parse_nq(DATASET),
+                    &options.to_dataset(),
+                    GraphScope::All,
+                )
+                .unwrap()
             )
             .to_string(),
             "b09ad7a64f32905af0ddada6082d9e7af89a001dc6d03b62d983036c9f98161b"
         );
     }
+
+    #[test]
+    fn create_verify_hash_input() {
+        let options = SignatureOptions::new(
+            "2024-01-01T00:00:00Z",
+            Iri::new("https://example.com/users/1#main-key").unwrap(),
+        )
+        .to_dataset();
+
+        const DATASET: &str = r#"
+            _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+        let dataset = parse_nq(DATASET);
+
+        let input = super::create_verify_hash_input(&dataset, &options).unwrap();
+        assert_eq!(input.len(), 128);
+        assert_eq!(
+            input,
+            super::hash_dataset_hex(&options).unwrap()
+                + &super::hash_dataset_hex(&dataset).unwrap(),
+        );
+        assert_eq!(
+            Sha256::digest(&input),
+            super::create_verify_hash::<Sha256, _, _>(&dataset, &options, GraphScope::All).unwrap(),
+        );
+    }
+
+    #[cfg(feature = "json-ld")]
+    #[test]
+    fn create_verify_hash_prehashed() {
+        let options = SignatureOptions::new(
+            "2024-01-01T00:00:00Z",
+            Iri::new("https://example.com/users/1#main-key").unwrap(),
+        )
+        .to_dataset();
+
+        const DATASET: &str = r#"
+            _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+        let dataset = parse_nq(DATASET);
+
+        let document_hex = hash_document_hex::<Sha256, _>(&dataset, GraphScope::All).unwrap();
+        assert_eq!(
+            super::create_verify_hash_prehashed(
+                &document_hex,
+                &options,
+                &mut Sha256::new(),
+                &mut Vec::new()
+            )
+            .unwrap(),
+            super::create_verify_hash::<Sha256, _, _>(&dataset, &options, GraphScope::All).unwrap(),
+        );
+    }
 }
@@ -2,6 +2,30 @@ use sophia_api::MownStr;
 
 pub const CREATED: MownStr<'_> = MownStr::from_str("http://purl.org/dc/terms/created");
 pub const CREATOR: MownStr<'_> = MownStr::from_str("http://purl.org/dc/terms/creator");
+pub const EXPIRATION: MownStr<'_> = MownStr::from_str("https://w3id.org/security#expiration");
 pub const DOMAIN: MownStr<'_> = MownStr::from_str("https://w3id.org/security#domain");
 pub const NONCE: MownStr<'_> = MownStr::from_str("https://w3id.org/security#nonce");
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub const SIGNATURE_VALUE: MownStr<'_> =
+    MownStr::from_str("https://w3id.org/security#signatureValue");
+#[cfg(any(feature = "sign", feature = "json-ld"))]
+pub const NORMALIZATION_ALGORITHM: MownStr<'_> =
+    MownStr::from_str("https://w3id.org/security#normalizationAlgorithm");
+#[cfg(any(feature = "sign", feature = "json-ld"))]
+pub const CANONICALIZATION_ALGORITHM: MownStr<'_> =
+    MownStr::from_str("https://w3id.org/security#canonicalizationAlgorithm");
+#[cfg(feature = "json-ld")]
+pub const OWNER: MownStr<'_> = MownStr::from_str("https://w3id.org/security#owner");
+#[cfg(feature = "json-ld")]
+pub const CONTROLLER: MownStr<'_> = MownStr::from_str("https://w3id.org/security#controller");
+#[cfg(feature = "json-ld")]
+pub const PUBLIC_KEY: MownStr<'_> = MownStr::from_str("https://w3id.org/security#publicKey");
+#[cfg(feature = "json-ld")]
+pub const PUBLIC_KEY_PEM: MownStr<'_> = MownStr::from_str("https://w3id.org/security#publicKeyPem");
 pub const DATETIME: MownStr<'_> = MownStr::from_str("http://www.w3.org/2001/XMLSchema#dateTime");
+#[cfg(any(feature = "json-ld", feature = "serde"))]
+pub const STRING: MownStr<'_> = MownStr::from_str("http://www.w3.org/2001/XMLSchema#string");
+pub const VERIFICATION_METHOD: MownStr<'_> =
+    MownStr::from_str("https://w3id.org/security#verificationMethod");
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub const TYPE: MownStr<'_> = MownStr::from_str("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
@@ -3,7 +3,7 @@ use std::error;
 use sophia_c14n::C14nError;
 
 /// Error while canonicalizing a dataset.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
 pub enum DatasetError<DE> {
     /// The dataset raised an error.
     #[error("Error from dataset: {0}")]
@@ -14,16 +14,44 @@ pub enum DatasetError<DE> {
     /// The canonicalization algorithm does not support this dataset.
     #[error("Unsupported feature: {0}")]
     Unsupported(String),
+    /// The dataset had more quads than the configured limit.
+    #[error("Too many quads (limit: {limit})")]
+    TooManyQuads {
+        /// The maximum number of quads allowed.
+        limit: usize,
+    },
 }
 
 impl<DE: error::Error> DatasetError<DE> {
     pub(crate) fn from_c14n_error(e: C14nError<DE>) -> Self {
         match e {
             C14nError::Dataset(e) => DatasetError::Dataset(e),
-            // We only write canonized outputs to `DigestWrite`, which never fails.
+            // We only write canonized outputs to `DigestWrite` or `Vec<u8>`, which never fail.
             C14nError::Io(_) => unreachable!(),
             C14nError::ToxicGraph(e) => DatasetError::ToxicGraph(e),
             C14nError::Unsupported(e) => DatasetError::Unsupported(e),
         }
     }
 }
+
+/// Error while parsing a [`SignatureType`](crate::SignatureType) from a string naming an unknown
+/// or unsupported signature suite.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("Unsupported signature type")]
+#[non_exhaustive]
+pub struct ParseSignatureTypeError;
+
+/// Error indicating that a key can't be used with a signature suite.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum KeyError {
+    /// The modulus of the RSA key is too small to fit the encoded digest of the signature
+    /// algorithm.
+    #[error("RSA modulus is too small ({bits} bits, but at least {minimum} bits are required)")]
+    ModulusTooSmall {
+        /// The size of the modulus in bits.
+        bits: usize,
+        /// The minimum size of the modulus in bits.
+        minimum: usize,
+    },
+}
@@ -0,0 +1,53 @@
+use base64::Engine as _;
+use rsa::pkcs8::EncodePublicKey;
+use rsa::RsaPublicKey;
+use sha2::{Digest, Sha256};
+
+/// The encoding of a key fingerprint returned by [`rsa_public_key_fingerprint`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FingerprintEncoding {
+    /// Lowercase hexadecimal.
+    #[default]
+    Hex,
+    /// Standard base64 without padding, as in the `SHA256:` fingerprints of OpenSSH.
+    Base64,
+}
+
+/// Returns the SHA-256 fingerprint of the `key`, i.e., the digest of its DER-encoded
+/// `SubjectPublicKeyInfo`, encoded with the `encoding`.
+///
+/// The fingerprint is a short, stable identifier of the key that can be logged in place of the
+/// whole modulus, and matches the one computed from the PEM-encoded public key by other tools,
+/// e.g., `openssl pkey -pubin -outform DER | sha256sum`.
+pub fn rsa_public_key_fingerprint(key: &RsaPublicKey, encoding: FingerprintEncoding) -> String {
+    // Encoding an `RsaPublicKey` never fails.
+    let der = key.to_public_key_der().unwrap();
+    let digest = Sha256::digest(der.as_bytes());
+    match encoding {
+        FingerprintEncoding::Hex => hex::encode(digest),
+        FingerprintEncoding::Base64 => {
+            base64::engine::general_purpose::STANDARD_NO_PAD.encode(digest)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::test::rsa_private_key;
+
+    use super::*;
+
+    #[test]
+    fn fingerprint() {
+        let key = rsa_private_key().to_public_key();
+        assert_eq!(
+            rsa_public_key_fingerprint(&key, FingerprintEncoding::Hex),
+            "1c1cd8de73fa0681787faf11a7783b80b52623cfee3696ba77c3772b6bea6f95",
+        );
+        assert_eq!(
+            rsa_public_key_fingerprint(&key, FingerprintEncoding::Base64),
+            "HBzY3nP6BoF4f68Rp3g7gLUmI8/uNpa6d8N3K2vqb5U",
+        );
+    }
+}
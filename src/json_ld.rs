@@ -1,4 +1,7 @@
+pub mod key_resolver;
 pub mod loader;
+#[cfg(feature = "serde_json")]
+mod sign_json;
 
 mod private {
     use sophia_jsonld::parser::JsonLdParser;
@@ -6,14 +9,18 @@ mod private {
     pub struct JsonLdOptions<LF>(pub(super) JsonLdParser<LF>);
 }
 
-use core::slice;
+use core::fmt::{self, Debug, Formatter};
+use core::{mem, slice};
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::convert::Infallible;
+use std::sync::Arc;
 
-use base64::Engine as _;
 use futures_util::future;
 use json_ld::RemoteDocument;
-use rsa::RsaPublicKey;
-use sophia_api::dataset::{CollectibleDataset, Dataset, MutableDataset, SetDataset};
+use json_syntax::Parse as _;
+use locspan::BorrowStripped;
+use sophia_api::dataset::{CollectibleDataset, Dataset, SetDataset};
 use sophia_api::quad::Quad;
 use sophia_api::source::{IntoQuadSource, QuadSource, StreamError};
 use sophia_api::term::{matcher, Term, TermKind};
@@ -24,18 +31,33 @@ use sophia_jsonld::vocabulary::ArcIri;
 use sophia_jsonld::{JsonLdError, JsonLdQuadSource};
 
 use crate::common::consts;
+use crate::common::{hash_document_hex, GraphScope};
+use crate::error::DatasetError;
+use crate::sign::CreatorKey;
+use crate::util::{decode_signature_value, Base64Debug};
+use crate::verify::AsRsaPublicKey;
 use crate::{verify, verify_rsa_signature_2017, SignatureType};
 
+use self::key_resolver::KeyResolver;
 use self::loader::PreloadedLoader;
 
+#[cfg(feature = "serde_json")]
+pub use self::sign_json::{sign_json, SignJsonError};
+
+const PLACEHOLDER_IRI: &str = "urn:x-placeholder";
+
 /// A signed document deserialized from JSON-LD.
-#[derive(Debug)]
-pub struct SignedDocument<D, O = D> {
+///
+/// The document is `Send` and `Sync` whenever its datasets and key resolver are, and so are the
+/// futures returned by the parsing and verifying methods, so that it can be used in
+/// multi-threaded async runtimes.
+pub struct SignedDocument<D, O = D, KR = ()> {
     document: D,
     signatures: Vec<Signature<O>>,
+    preloaded_contexts: Vec<Iri<Box<str>>>,
+    key_resolver: KR,
 }
 
-#[derive(Debug)]
 pub struct Signature<O> {
     options: O,
     id: Option<Box<str>>,
@@ -43,9 +65,15 @@ pub struct Signature<O> {
     signature_value: Vec<u8>,
 }
 
-pub struct DocumentParser<LF = DefaultLoaderFactory<PreloadedLoader>, OO = UseDocumentOptions> {
+pub struct DocumentParser<
+    LF = DefaultLoaderFactory<PreloadedLoader>,
+    OO = UseDocumentOptions,
+    KR = (),
+> {
     parser: JsonLdParser<LF>,
     options_parser: OO,
+    key_resolver: KR,
+    signature_key: Box<str>,
 }
 
 #[non_exhaustive]
@@ -56,20 +84,90 @@ pub struct UseDocumentOptions;
 pub enum Error<DE, OE> {
     MissingSignatureOptions,
     UnsupportedType,
+    UnsupportedProofFormat,
     NestingSignatureNode,
     DuplicateSignatures,
     BadSubject,
     BadSignatureOptions,
     BadSignatureValue,
+    UnsupportedCanonicalization(Box<str>),
+    Json(json_syntax::parse::MetaError<locspan::Location<ArcIri>>),
     Document(JsonLdError),
     Options(JsonLdError),
     DocumentDataset(DE),
     OptionsDataset(OE),
+    Verify(verify::Error<DE, OE>),
+}
+
+impl<DE, OE> From<verify::Error<DE, OE>> for Error<DE, OE> {
+    fn from(e: verify::Error<DE, OE>) -> Self {
+        Error::Verify(e)
+    }
+}
+
+/// Compares the errors by their variants and payloads, except that the `Json`, `Document` and
+/// `Options` variants compare equal to the same variants regardless of their payloads, which aren't
+/// comparable.
+impl<DE: PartialEq, OE: PartialEq> PartialEq for Error<DE, OE> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Error::UnsupportedCanonicalization(a), Error::UnsupportedCanonicalization(b)) => {
+                a == b
+            }
+            (Error::DocumentDataset(a), Error::DocumentDataset(b)) => a == b,
+            (Error::OptionsDataset(a), Error::OptionsDataset(b)) => a == b,
+            (Error::Verify(a), Error::Verify(b)) => a == b,
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+impl<DE: Eq, OE: Eq> Eq for Error<DE, OE> {}
+
+/// The `created` value of a signature, as returned by [`Signature::created_lenient`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Created {
+    /// The lexical form of the literal.
+    pub value: Box<str>,
+    /// Whether the literal was a plain string rather than an `xsd:dateTime`.
+    pub untyped: bool,
+}
+
+/// Error while verifying a signature with a key resolved by a [`KeyResolver`].
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyResolvedError<DE, OE, KE> {
+    /// The signature options had neither a `creator` nor a `verificationMethod`.
+    #[error("Signature options have no creator")]
+    MissingCreator,
+    /// The key resolver raised an error.
+    #[error("Unable to resolve the key: {0}")]
+    KeyResolution(KE),
+    /// The signature didn't verify.
+    #[error(transparent)]
+    Verify(verify::Error<DE, OE>),
 }
 
 pub type JsonLdOptions<LF = DefaultLoaderFactory<PreloadedLoader>> =
     sophia_jsonld::options::JsonLdOptions<LF>;
 
+/// The loader factory of [`DocumentParser::preloaded_then_http`].
+#[cfg(feature = "http")]
+pub type PreloadedThenHttpLoaderFactory =
+    DefaultLoaderFactory<sophia_jsonld::loader::ChainLoader<PreloadedLoader, loader::HttpLoader>>;
+
+/// Removes the top-level `signature` entries of the JSON `document`, e.g., before re-signing it, and
+/// returns the number of the removed entries.
+///
+/// Nested `signature` entries are left as is, like the JSON-LD parser of this crate, which only
+/// excludes the top-level entries from the signed document. Does nothing if the `document` is not
+/// an object.
+pub fn remove_signature_entries<M>(document: &mut json_syntax::Value<M>) -> usize {
+    document
+        .as_object_mut()
+        .map_or(0, |object| object.remove("signature").count())
+}
+
 impl<LF> DocumentParser<LF>
 where
     LF: LoaderFactory + Default,
@@ -79,6 +177,20 @@ where
     }
 }
 
+#[cfg(feature = "http")]
+impl DocumentParser<PreloadedThenHttpLoaderFactory> {
+    /// Creates a parser that serves the well-known contexts with [`PreloadedLoader`] and fetches
+    /// the other remote contexts over HTTP with [`HttpLoader`](loader::HttpLoader).
+    ///
+    /// This is the recommended loader configuration for verifying real-world documents, which may
+    /// refer to arbitrary contexts, while still verifying the documents referring to the contexts
+    /// whose original hosts have gone away. Note that fetching the contexts lets the documents make
+    /// the verifier send requests to arbitrary hosts.
+    pub fn preloaded_then_http() -> Self {
+        Self::new()
+    }
+}
+
 impl<LF> DocumentParser<LF>
 where
     LF: LoaderFactory,
@@ -87,82 +199,142 @@ where
         Self {
             parser: JsonLdParser::new_with_options(options),
             options_parser: UseDocumentOptions,
+            key_resolver: (),
+            signature_key: "signature".into(),
         }
     }
 }
 
-impl<LF, OO> DocumentParser<LF, OO> {
+impl<LF, OO, KR> DocumentParser<LF, OO, KR> {
     pub fn options(&self) -> &JsonLdOptions<LF> {
         self.parser.options()
     }
 
-    pub fn set_options<LF2>(self, options: JsonLdOptions<LF2>) -> DocumentParser<LF2, OO>
+    pub fn set_options<LF2>(self, options: JsonLdOptions<LF2>) -> DocumentParser<LF2, OO, KR>
     where
         LF2: LoaderFactory,
     {
         DocumentParser {
             parser: JsonLdParser::new_with_options(options),
             options_parser: self.options_parser,
+            key_resolver: self.key_resolver,
+            signature_key: self.signature_key,
         }
     }
 
     pub fn set_options_options<OLF>(
         self,
         options_options: JsonLdOptions<OLF>,
-    ) -> DocumentParser<LF, private::JsonLdOptions<OLF>>
+    ) -> DocumentParser<LF, private::JsonLdOptions<OLF>, KR>
     where
         OLF: LoaderFactory,
     {
         DocumentParser {
             parser: self.parser,
             options_parser: private::JsonLdOptions(JsonLdParser::new_with_options(options_options)),
+            key_resolver: self.key_resolver,
+            signature_key: self.signature_key,
         }
     }
 
-    pub fn unset_options_options(self) -> DocumentParser<LF> {
+    pub fn unset_options_options(self) -> DocumentParser<LF, UseDocumentOptions, KR> {
         DocumentParser {
             parser: self.parser,
             options_parser: UseDocumentOptions,
+            key_resolver: self.key_resolver,
+            signature_key: self.signature_key,
+        }
+    }
+
+    /// Sets a resolver of the public keys of the signatures, which is passed on to the parsed
+    /// [`SignedDocument`] and used by [`SignedDocument::verify_resolved`].
+    pub fn with_key_resolver<KR2>(self, key_resolver: KR2) -> DocumentParser<LF, OO, KR2>
+    where
+        KR2: KeyResolver,
+    {
+        DocumentParser {
+            parser: self.parser,
+            options_parser: self.options_parser,
+            key_resolver,
+            signature_key: self.signature_key,
+        }
+    }
+
+    /// Sets the key of the top-level entry that holds the signatures, which defaults to
+    /// `"signature"`.
+    ///
+    /// This is only meant for the documents of non-standard producers that put the signatures
+    /// under another key, e.g., `"proof"`. Like the `signature` entry, the entry is lexically
+    /// removed from the document, so the key should be a term that the document context maps to
+    /// the same property as the signatures would otherwise be.
+    pub fn signature_key(self, signature_key: impl Into<Box<str>>) -> Self {
+        Self {
+            signature_key: signature_key.into(),
+            ..self
         }
     }
 }
 
-impl<LF, OLF> DocumentParser<LF, private::JsonLdOptions<OLF>> {
+impl<LF, OLF, KR> DocumentParser<LF, private::JsonLdOptions<OLF>, KR> {
     pub fn options_options(&self) -> &JsonLdOptions<OLF> {
         self.options_parser.0.options()
     }
 }
 
-impl<LF> DocumentParser<LF, UseDocumentOptions>
+impl<LF, KR> DocumentParser<LF, UseDocumentOptions, KR>
 where
     LF: LoaderFactory,
+    KR: Clone,
 {
+    /// Parses a signed document.
+    ///
+    /// The parser can be reused to parse many documents. The key resolver is cloned into each of
+    /// the parsed documents.
     pub async fn parse<D, O>(
-        self,
+        &self,
         document: RemoteDocument<ArcIri>,
-    ) -> Result<SignedDocument<D, O>, Error<D::Error, O::Error>>
+    ) -> Result<SignedDocument<D, O, KR>, Error<D::Error, O::Error>>
     where
         D: CollectibleDataset + SetDataset,
-        O: CollectibleDataset + SetDataset + MutableDataset,
+        O: CollectibleDataset + SetDataset,
     {
-        parse(document, &self.parser, &self.parser).await
+        parse(
+            document,
+            &self.parser,
+            &self.parser,
+            self.key_resolver.clone(),
+            &self.signature_key,
+        )
+        .await
     }
 }
 
-impl<LF, OLF> DocumentParser<LF, private::JsonLdOptions<OLF>>
+impl<LF, OLF, KR> DocumentParser<LF, private::JsonLdOptions<OLF>, KR>
 where
     LF: LoaderFactory,
     OLF: LoaderFactory,
+    KR: Clone,
 {
+    /// Parses a signed document.
+    ///
+    /// The parser can be reused to parse many documents. The key resolver is cloned into each of
+    /// the parsed documents.
     pub async fn parse<D, O>(
-        self,
+        &self,
         document: RemoteDocument<ArcIri>,
-    ) -> Result<SignedDocument<D, O>, Error<D::Error, O::Error>>
+    ) -> Result<SignedDocument<D, O, KR>, Error<D::Error, O::Error>>
     where
         D: CollectibleDataset + SetDataset,
-        O: CollectibleDataset + SetDataset + MutableDataset,
+        O: CollectibleDataset + SetDataset,
     {
-        parse(document, &self.parser, &self.options_parser.0).await
+        parse(
+            document,
+            &self.parser,
+            &self.options_parser.0,
+            self.key_resolver.clone(),
+            &self.signature_key,
+        )
+        .await
     }
 }
 
@@ -183,28 +355,97 @@ impl SignedDocument<()> {
 
 impl<D, O> SignedDocument<D, O>
 where
-    D: CollectibleDataset + SetDataset + MutableDataset,
-    O: CollectibleDataset + SetDataset + MutableDataset,
+    D: CollectibleDataset + SetDataset,
+    O: CollectibleDataset + SetDataset,
 {
     pub async fn parse(
         document: RemoteDocument<ArcIri>,
     ) -> Result<Self, Error<D::Error, O::Error>> {
         SignedDocument::parser().parse(document).await
     }
+
+    /// Parses a signed document from a JSON string.
+    ///
+    /// The `base` IRI is used as the URL of the document, i.e., the base IRI to resolve relative
+    /// IRI references in the document against. If `None`, the document has no URL and a
+    /// placeholder IRI is used for the locations in error reports.
+    pub async fn parse_json_str(
+        json: &str,
+        base: Option<ArcIri>,
+    ) -> Result<Self, Error<D::Error, O::Error>> {
+        let location_iri = base
+            .clone()
+            .unwrap_or_else(|| Iri::new_unchecked(Arc::from(PLACEHOLDER_IRI)));
+        let json = json_syntax::Value::parse_str(json, |span| {
+            locspan::Location::new(location_iri.clone(), span)
+        })
+        .map_err(Error::Json)?;
+        Self::parse(RemoteDocument::new(base, None, json)).await
+    }
 }
 
-impl<D, O> SignedDocument<D, O>
+impl<D, O, KR> SignedDocument<D, O, KR> {
+    /// Returns the document dataset with the `signature` node removed.
+    pub fn document(&self) -> &D {
+        &self.document
+    }
+
+    /// Returns the signatures of the document.
+    pub fn signatures(&self) -> &[Signature<O>] {
+        &self.signatures
+    }
+
+    /// Returns the IRIs of the contexts that [`PreloadedLoader`] served from its cache while
+    /// expanding the document and its signature options, in the order of the first use.
+    ///
+    /// The verification of such a document relies on the cached contexts, which may differ from
+    /// what the IRIs currently serve. Empty if the loader of the parser doesn't involve
+    /// `PreloadedLoader`.
+    pub fn preloaded_contexts(&self) -> &[Iri<Box<str>>] {
+        &self.preloaded_contexts
+    }
+}
+
+impl<D, O, KR> SignedDocument<D, O, KR>
+where
+    O: Dataset,
+{
+    /// Returns an iterator over the `verificationMethod`s or `creator`s of the signatures, skipping
+    /// the signatures without either.
+    ///
+    /// This is useful to decide which keys to fetch before verifying the signatures.
+    pub fn creators(&self) -> impl Iterator<Item = Result<IriRef<Box<str>>, O::Error>> + '_ {
+        self.signatures
+            .iter()
+            .filter_map(Signature::verification_method)
+    }
+}
+
+impl<D, O, KR> SignedDocument<D, O, KR>
 where
     D: SetDataset,
     O: SetDataset,
 {
-    pub fn verify_rsa_signature_2017(
+    /// Verifies all the signatures of the document with the `key`.
+    ///
+    /// The document is canonicalized only once and its hash is shared across the signatures.
+    pub fn verify_rsa_signature_2017<K>(
         &self,
-        key: &RsaPublicKey,
-    ) -> Result<(), verify::Error<D::Error, O::Error>> {
+        key: &K,
+    ) -> Result<(), verify::Error<D::Error, O::Error>>
+    where
+        K: AsRsaPublicKey + ?Sized,
+    {
+        let key = key.as_rsa_public_key();
+        if self.signatures.is_empty() {
+            return Ok(());
+        }
+
+        let document_hex = self.hash_document()?;
+        let mut verifier = <verify::Verifier<'_>>::default();
         for signature in &self.signatures {
-            verify_rsa_signature_2017(
-                &self.document,
+            verifier.verify_prehashed(
+                &document_hex,
                 &signature.options,
                 key,
                 &signature.signature_value,
@@ -212,6 +453,166 @@ where
         }
         Ok(())
     }
+
+    /// Returns the hex-encoded hash of the document to be shared across the signatures.
+    fn hash_document<OE>(&self) -> Result<Vec<u8>, verify::Error<D::Error, OE>> {
+        hash_document_hex::<sha2::Sha256, _>(&self.document, GraphScope::All)
+            .map_err(verify::Error::Dataset)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<D, O, KR> SignedDocument<D, O, KR>
+where
+    D: SetDataset,
+    D::Error: Send,
+    O: SetDataset + Sync,
+    O::Error: Send,
+{
+    /// Verifies all the signatures of the document like
+    /// [`verify_rsa_signature_2017`](Self::verify_rsa_signature_2017), but on the `rayon` thread
+    /// pool.
+    ///
+    /// The document is canonicalized only once and its hash is shared across the signatures. If
+    /// more than one signature fails, it is unspecified which of the errors is returned.
+    pub fn verify_all_parallel<K>(&self, key: &K) -> Result<(), verify::Error<D::Error, O::Error>>
+    where
+        K: AsRsaPublicKey + ?Sized,
+    {
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+        let key = key.as_rsa_public_key();
+        if self.signatures.is_empty() {
+            return Ok(());
+        }
+
+        let document_hex = self.hash_document()?;
+        self.signatures.par_iter().try_for_each_init(
+            <verify::Verifier<'_>>::default,
+            |verifier, signature| {
+                verifier.verify_prehashed(
+                    &document_hex,
+                    &signature.options,
+                    key,
+                    &signature.signature_value,
+                )
+            },
+        )
+    }
+}
+
+impl<D, O, KR> SignedDocument<D, O, KR>
+where
+    D: SetDataset,
+    O: SetDataset,
+    KR: KeyResolver,
+{
+    /// Verifies the signatures with the public keys resolved from their `verificationMethod`s or
+    /// `creator`s by the key resolver set with [`DocumentParser::with_key_resolver`].
+    pub async fn verify_resolved(
+        &self,
+    ) -> Result<(), VerifyResolvedError<D::Error, O::Error, KR::Error>> {
+        if self.signatures.is_empty() {
+            return Ok(());
+        }
+
+        let document_hex = self.hash_document().map_err(VerifyResolvedError::Verify)?;
+        let mut verifier = <verify::Verifier<'_>>::default();
+        for signature in &self.signatures {
+            let creator = signature
+                .verification_method()
+                .ok_or(VerifyResolvedError::MissingCreator)?
+                .map_err(|e| {
+                    VerifyResolvedError::Verify(verify::Error::Options(DatasetError::Dataset(e)))
+                })?;
+            let key = self
+                .key_resolver
+                .resolve(creator.as_ref())
+                .await
+                .map_err(VerifyResolvedError::KeyResolution)?;
+            verifier
+                .verify_prehashed(
+                    &document_hex,
+                    &signature.options,
+                    &key,
+                    &signature.signature_value,
+                )
+                .map_err(VerifyResolvedError::Verify)?;
+        }
+        Ok(())
+    }
+}
+
+impl<D, O, KR> Debug for SignedDocument<D, O, KR>
+where
+    D: Debug,
+    O: Dataset,
+    KR: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignedDocument")
+            .field("document", &self.document)
+            .field("signatures", &self.signatures)
+            .field("preloaded_contexts", &self.preloaded_contexts)
+            .field("key_resolver", &self.key_resolver)
+            .finish()
+    }
+}
+
+impl<O> Signature<O>
+where
+    O: SetDataset,
+{
+    /// Verifies this signature for the `document` with the `key`.
+    ///
+    /// This is useful to verify a specific signature of a [`SignedDocument`], e.g., one selected
+    /// by its [`creator`](Self::creator). Pass [`SignedDocument::document`] as the `document`.
+    pub fn verify<D, K>(
+        &self,
+        document: &D,
+        key: &K,
+    ) -> Result<(), verify::Error<D::Error, O::Error>>
+    where
+        D: SetDataset,
+        K: AsRsaPublicKey + ?Sized,
+    {
+        match self.kind {
+            SignatureType::RsaSignature2017 => {
+                verify_rsa_signature_2017(document, &self.options, key, &self.signature_value)
+            }
+        }
+    }
+
+    /// Returns whether this signature describes the same signature as `other`, e.g., the one it
+    /// was serialized from.
+    ///
+    /// See [`sign::Signature::semantically_eq`](crate::sign::Signature::semantically_eq) for the
+    /// details of the comparison.
+    pub fn semantically_eq(
+        &self,
+        other: &crate::Signature<'_>,
+    ) -> Result<bool, DatasetError<O::Error>> {
+        if self.kind != other.kind {
+            return Ok(false);
+        }
+        other.semantically_eq(&self.options, &self.signature_value)
+    }
+}
+
+impl<O: Dataset> Debug for Signature<O> {
+    /// Summarizes the signature options by the well-known ones instead of dumping the dataset.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signature")
+            .field("id", &self.id)
+            .field("kind", &self.kind)
+            .field("created", &self.created().and_then(Result::ok))
+            .field(
+                "creator",
+                &self.creator().and_then(Result::ok).map(IriRef::unwrap),
+            )
+            .field("signature_value", &Base64Debug(&self.signature_value))
+            .finish_non_exhaustive()
+    }
 }
 
 impl<O> Signature<O>
@@ -236,6 +637,39 @@ where
             .next()
     }
 
+    /// Like [`created`](Self::created), but falls back to a plain string `created` literal if there
+    /// is no `xsd:dateTime` one.
+    ///
+    /// A producer may emit `created` as a plain string when its context lacks the type coercion of
+    /// the term. Such a literal still participates in the hash being verified, and the returned
+    /// [`Created::untyped`] flag tells that the datatype was missing.
+    pub fn created_lenient(&self) -> Option<Result<Created, O::Error>> {
+        if let Some(created) = self.created() {
+            return Some(created.map(|value| Created {
+                value,
+                untyped: false,
+            }));
+        }
+        self.options
+            .quads_matching(
+                matcher::Any,
+                [Iri::new_unchecked(consts::CREATED)],
+                matcher::DatatypeMatcher::new(IriRef::new_unchecked(consts::STRING)),
+                matcher::Any,
+            )
+            .filter_map(|q| {
+                q.map(|q| q.to_o().lexical_form().map(Into::into))
+                    .transpose()
+            })
+            .next()
+            .map(|created| {
+                created.map(|value| Created {
+                    value,
+                    untyped: true,
+                })
+            })
+    }
+
     pub fn creator(&self) -> Option<Result<IriRef<Box<str>>, O::Error>> {
         self.options
             .quads_matching(
@@ -251,11 +685,12 @@ where
             .next()
     }
 
-    pub fn domain(&self) -> Option<Result<Box<str>, O::Error>> {
+    /// Returns the `expires` datetime (`sec:expiration`) of the signature, if any.
+    pub fn expires(&self) -> Option<Result<Box<str>, O::Error>> {
         self.options
             .quads_matching(
                 matcher::Any,
-                [Iri::new_unchecked(consts::DOMAIN)],
+                [Iri::new_unchecked(consts::EXPIRATION)],
                 TermKind::Literal,
                 matcher::Any,
             )
@@ -266,6 +701,51 @@ where
             .next()
     }
 
+    /// Returns the `verificationMethod` of the signature, falling back to its
+    /// [`creator`](Self::creator).
+    ///
+    /// Newer Linked Data Proofs use the former in place of the latter to identify the signing key.
+    pub fn verification_method(&self) -> Option<Result<IriRef<Box<str>>, O::Error>> {
+        self.options
+            .quads_matching(
+                matcher::Any,
+                [Iri::new_unchecked(consts::VERIFICATION_METHOD)],
+                TermKind::Iri,
+                matcher::Any,
+            )
+            .filter_map(|q| {
+                q.map(|q| q.to_o().iri().map(|iri| iri.map_unchecked(Into::into)))
+                    .transpose()
+            })
+            .next()
+            .or_else(|| self.creator())
+    }
+
+    /// Returns the `domain` of the signature, if any.
+    ///
+    /// Some implementations set the domain as a string literal and others as an IRI, e.g., one
+    /// identifying the scope of the signature, so this returns the lexical form of a literal or the
+    /// IRI, whichever comes first.
+    pub fn domain(&self) -> Option<Result<Box<str>, O::Error>> {
+        self.options
+            .quads_matching(
+                matcher::Any,
+                [Iri::new_unchecked(consts::DOMAIN)],
+                matcher::Any,
+                matcher::Any,
+            )
+            .filter_map(|q| {
+                q.map(|q| {
+                    let o = q.to_o();
+                    o.lexical_form()
+                        .map(|lexical_form| lexical_form.into())
+                        .or_else(|| o.iri().map(|iri| iri.as_str().into()))
+                })
+                .transpose()
+            })
+            .next()
+    }
+
     pub fn nonce(&self) -> Option<Result<Box<str>, O::Error>> {
         self.options
             .quads_matching(
@@ -280,16 +760,167 @@ where
             })
             .next()
     }
+
+    /// Returns an iterator over the quads of the signature options other than the `created`,
+    /// `expires`, `creator`, `verificationMethod`, `domain` and `nonce` properties, which this crate
+    /// doesn't model.
+    ///
+    /// Note that these quads are part of the signature options and do contribute to the hash being
+    /// verified, so they are as secured by the signature as the known properties are.
+    pub fn other_options(&self) -> impl Iterator<Item = Result<O::Quad<'_>, O::Error>> {
+        self.options.quads().filter(|q| {
+            q.as_ref().map_or(true, |q| {
+                let p = q.p();
+                ![
+                    consts::CREATED,
+                    consts::EXPIRATION,
+                    consts::CREATOR,
+                    consts::VERIFICATION_METHOD,
+                    consts::DOMAIN,
+                    consts::NONCE,
+                ]
+                .into_iter()
+                .any(|iri| p.eq(Iri::new_unchecked(iri)))
+            })
+        })
+    }
+
+    /// Converts the signature into a [`sign::Signature`](crate::Signature) owning its options,
+    /// e.g., to re-serialize it or to compare or re-verify it with the dataset-level APIs.
+    ///
+    /// Returns `None` if the options lack an `xsd:dateTime` `created` or an absolute `creator` (or
+    /// `verificationMethod`) IRI, which the `sign::Signature` requires. The
+    /// [other options](Self::other_options) are carried over as its `extra_options`, so the
+    /// [options dataset](crate::Signature::options_dataset) of the returned signature hashes the
+    /// same as this one as long as the `domain` and `nonce` are plain string literals.
+    ///
+    /// For the signature options alone, borrow them from the returned signature as a
+    /// [`SignatureOptions`](crate::SignatureOptions) with `SignatureOptions::from(&signature)`.
+    /// Its [`to_dataset`](crate::SignatureOptions::to_dataset) builds the same options dataset.
+    #[doc(alias = "to_signature_options")]
+    pub fn to_signature(&self) -> Result<Option<crate::Signature<'static>>, O::Error> {
+        let Some(created) = self.created().transpose()? else {
+            return Ok(None);
+        };
+        let verification_method = self
+            .options
+            .quads_matching(
+                matcher::Any,
+                [Iri::new_unchecked(consts::VERIFICATION_METHOD)],
+                TermKind::Iri,
+                matcher::Any,
+            )
+            .next()
+            .transpose()?
+            .map(|q| {
+                q.to_o()
+                    .iri()
+                    .unwrap()
+                    .map_unchecked(|iri| Box::from(&*iri))
+            });
+        let mut extra_options = Vec::new();
+        let (creator_key, creator) = match (self.creator().transpose()?, verification_method) {
+            (Some(creator), None) => (CreatorKey::Creator, creator),
+            (None, Some(verification_method)) => {
+                (CreatorKey::VerificationMethod, verification_method)
+            }
+            (Some(creator), Some(verification_method)) => {
+                let Ok(verification_method) = Iri::new(verification_method.unwrap()) else {
+                    return Ok(None);
+                };
+                extra_options.push((
+                    Iri::new_unchecked(consts::VERIFICATION_METHOD.to_string().into()),
+                    verification_method.map_unchecked(String::from).into_term(),
+                ));
+                (CreatorKey::Creator, creator)
+            }
+            (None, None) => return Ok(None),
+        };
+        let Ok(creator) = Iri::new(Cow::Owned(creator.unwrap().into())) else {
+            return Ok(None);
+        };
+        for quad in self.other_options() {
+            let ([_, p, o], _) = quad?.to_spog();
+            // Predicates are always IRIs in RDF.
+            let p = p.iri().unwrap();
+            extra_options.push((
+                Iri::new_unchecked(Cow::Owned(p.as_str().into())),
+                o.into_term(),
+            ));
+        }
+
+        Ok(Some(crate::Signature {
+            #[cfg(feature = "serde")]
+            context: Default::default(),
+            kind: match self.kind {
+                SignatureType::RsaSignature2017 => SignatureType::RsaSignature2017,
+            },
+            #[cfg(feature = "serde")]
+            type_as_array: false,
+            #[cfg(feature = "serde")]
+            signature_encoding: Default::default(),
+            created: Cow::Owned(created.into()),
+            expires: self.expires().transpose()?.map(|e| Cow::Owned(e.into())),
+            creator,
+            creator_key,
+            extra_options,
+            domain: self.domain().transpose()?.map(|d| Cow::Owned(d.into())),
+            nonce: self.nonce().transpose()?.map(|n| Cow::Owned(n.into())),
+            signature_value: self.signature_value.clone(),
+            control_char_literals: Vec::new(),
+        }))
+    }
+}
+
+/// Prepends the document context `dc` to the signature options context `oc`.
+///
+/// Either context may be an array, a string or an (inline) object. The entries of `dc` that also
+/// appear in `oc` are removed so that the merged context doesn't contain duplicates. Keeping the
+/// last occurrences preserves the precedence of the term definitions in `oc`, as the later
+/// contexts override the earlier ones.
+///
+/// The contexts come from untrusted documents, so this runs in time linear to their sizes.
+fn merge_context<M: Clone>(
+    dc: &locspan::Meta<json_syntax::Value<M>, M>,
+    oc: &mut locspan::Meta<json_syntax::Value<M>, M>,
+) {
+    let dc = json_syntax::Value::force_as_array(dc);
+    let oc_orig = match oc.take() {
+        json_syntax::Value::Array(array) => array,
+        value => vec![locspan::Meta(value, oc.1.clone())],
+    };
+    let oc_set: HashSet<_> = oc_orig.iter().map(BorrowStripped::stripped).collect();
+    let mut oc_new: Vec<_> = dc
+        .iter()
+        .filter(|&c| !oc_set.contains(c.stripped()))
+        .cloned()
+        .collect();
+    oc_new.extend(oc_orig);
+    oc.0 = json_syntax::Value::Array(oc_new);
+}
+
+/// Returns the first supported signature type among the value of a `type` entry, which may be
+/// either a string or an array of strings.
+fn signature_type<M>(ty: &json_syntax::Value<M>) -> Option<SignatureType> {
+    match *ty {
+        json_syntax::Value::String(ref ty) => ty.parse().ok(),
+        json_syntax::Value::Array(ref types) => types
+            .iter()
+            .find_map(|ty| ty.as_string().and_then(|ty| ty.parse().ok())),
+        _ => None,
+    }
 }
 
-async fn parse<D, O, LF, OLF>(
+async fn parse<D, O, LF, OLF, KR>(
     document: RemoteDocument<ArcIri>,
     parser: &JsonLdParser<LF>,
     options_parser: &JsonLdParser<OLF>,
-) -> Result<SignedDocument<D, O>, Error<D::Error, O::Error>>
+    key_resolver: KR,
+    signature_key: &str,
+) -> Result<SignedDocument<D, O, KR>, Error<D::Error, O::Error>>
 where
     D: CollectibleDataset + SetDataset,
-    O: CollectibleDataset + SetDataset + MutableDataset,
+    O: CollectibleDataset + SetDataset,
     LF: LoaderFactory,
     OLF: LoaderFactory,
 {
@@ -317,8 +948,12 @@ where
     // entry value contains nested node objects, in which case the intended semantics is unclear and
     // we conservatively treat it as an error (which we'll check later after deserializing the
     // signature options as RDF).
-    let mut signatures = if let Some(signature_entry) = document_object.remove("signature").last() {
+    let mut signatures = if let Some(signature_entry) = document_object.remove(signature_key).last()
+    {
         signature_entry.value
+    } else if document_object.get_entries("proof").next().is_some() {
+        // The newer Data Integrity `proof` format, which we don't support.
+        return Err(Error::UnsupportedProofFormat);
     } else {
         return Err(Error::MissingSignatureOptions);
     };
@@ -362,19 +997,7 @@ where
             // unfortunately prevalent in the wild.
             if let Some(dce) = document_context_entry {
                 if let Some(oc) = options_object.get_mut("@context").last() {
-                    let dc = json_syntax::Value::force_as_array(&dce.value);
-                    if let Some(array) = oc.as_array_mut() {
-                        array.reserve(dc.len());
-                        for c in dc.iter().rev() {
-                            array.insert(0, c.clone());
-                        }
-                    } else {
-                        let oc_orig = oc.take();
-                        let mut oc_new = Vec::with_capacity(dc.len() + 1);
-                        oc_new.extend_from_slice(&dc);
-                        oc_new.push(locspan::Meta(oc_orig, oc.1.clone()));
-                        oc.0 = json_syntax::Value::Array(oc_new);
-                    }
+                    merge_context(&dce.value, oc);
                 } else {
                     options_object.push_entry(dce.clone());
                 }
@@ -385,20 +1008,11 @@ where
             // Here, we are lexically removing the entries just like we did for the `signature`
             // entry.
 
-            let is_rsa_signature_2017 =
-                options_object
-                    .remove("type")
-                    .last()
-                    .map_or(false, |ty| match ty.value.0 {
-                        json_syntax::Value::String(ref ty) => ty == "RsaSignature2017",
-                        json_syntax::Value::Array(ref types) => types
-                            .iter()
-                            .any(|ty| ty.as_string() == Some("RsaSignature2017")),
-                        _ => false,
-                    });
-            if !is_rsa_signature_2017 {
-                return Err(Error::UnsupportedType);
-            }
+            let kind = options_object
+                .remove("type")
+                .last()
+                .and_then(|ty| signature_type(&ty.value))
+                .ok_or(Error::UnsupportedType)?;
 
             let id = if let Some(entry) = options_object.remove("id").last() {
                 entry.value.0.into_string().map(|s| s.into_boxed_str())
@@ -412,11 +1026,9 @@ where
                         .value
                         .0
                         .into_string()
-                        .and_then(|v| {
-                            base64::engine::general_purpose::STANDARD
-                                .decode(v.as_bytes())
-                                .ok()
-                        })
+                        .and_then(|v| decode_signature_value(v.as_bytes()))
+                        // An empty signature can never verify.
+                        .filter(|v| !v.is_empty())
                         .ok_or(Error::BadSignatureValue)?
                 } else {
                     return Err(Error::MissingSignatureOptions);
@@ -431,7 +1043,7 @@ where
                     locspan::Meta(options.take(), options.1.clone()),
                 ),
                 id,
-                kind: SignatureType::RsaSignature2017,
+                kind,
                 signature_value,
             })
         })
@@ -463,7 +1075,9 @@ where
             })
         },
     ));
-    let (document, signatures) = future::try_join(document_fut, signatures_fut).await?;
+    let (result, preloaded_contexts) =
+        loader::preloaded::record_hits(future::try_join(document_fut, signatures_fut)).await;
+    let (document, signatures) = result?;
 
     let document = D::from_quad_source(document).map_err(|e| match e {
         StreamError::SourceError(e) => match e {},
@@ -485,22 +1099,33 @@ where
                 })?;
                 let mut signature_subject: Option<<O::Quad<'_> as Quad>::Term> = None;
                 for quad in options.quads() {
-                    let ([s, _, _], g) = quad.map_err(Error::OptionsDataset)?.to_spog();
+                    let ([s, p, o], g) = quad.map_err(Error::OptionsDataset)?.to_spog();
+                    if p.eq(Iri::new_unchecked(consts::NORMALIZATION_ALGORITHM))
+                        || p.eq(Iri::new_unchecked(consts::CANONICALIZATION_ALGORITHM))
+                    {
+                        check_canonicalization_algorithm(o)
+                            .map_err(Error::UnsupportedCanonicalization)?;
+                    }
+                    // Named graphs shouldn't appear in a typically-structured document without a
+                    // top-level `@graph` entry.
+                    if g.is_some() {
+                        return Err(Error::NestingSignatureNode);
+                    }
                     if let Some(ref ss) = signature_subject {
-                        if !ss.eq(s) || g.is_some() {
+                        // More than one subject implies that an entry value contains a nested node.
+                        if !ss.eq(s) {
                             return Err(Error::NestingSignatureNode);
                         }
-                    } else if !s.is_blank_node() || g.is_some() {
-                        // The signature node should have a blank node identifier since we have removed the `id`
-                        // entry. A different term kind implies that either the `@id` is assigned in another way
-                        // or the entry value contains a nested node. Also, named graphs shouldn't appear in a
-                        // typically-structured document without a top-level `@graph` entry.
-                        return Err(Error::NestingSignatureNode);
                     } else {
                         signature_subject = Some(s);
                     }
                 }
-                drop(signature_subject);
+                // The signature node should have a blank node identifier since we have removed the
+                // `id` entry. A different term kind implies that the `@id` is assigned in another
+                // way.
+                if signature_subject.is_some_and(|s| !s.is_blank_node()) {
+                    return Err(Error::BadSubject);
+                }
 
                 Ok(Signature {
                     options,
@@ -515,9 +1140,40 @@ where
     Ok(SignedDocument {
         document,
         signatures,
+        // The loader only serves valid IRIs.
+        preloaded_contexts: preloaded_contexts
+            .into_iter()
+            .map(Iri::new_unchecked)
+            .collect(),
+        key_resolver,
     })
 }
 
+/// Checks that the value of a `normalizationAlgorithm` or `canonicalizationAlgorithm` property
+/// names an algorithm that our canonicalization (RDFC 1.0) is compatible with, returning the name
+/// of the algorithm otherwise.
+fn check_canonicalization_algorithm<T: Term>(algorithm: T) -> Result<(), Box<str>> {
+    let name = if let Some(name) = algorithm.lexical_form() {
+        name
+    } else if let Some(iri) = algorithm.iri() {
+        iri.unwrap()
+    } else if let Some(id) = algorithm.bnode_id() {
+        return Err(format!("_:{}", id.as_str()).into());
+    } else {
+        return Err("".into());
+    };
+
+    // RDFC 1.0 is a successor of URDNA2015 and practically compatible with GCA2015 (see the Caveats
+    // section of the crate's documentation).
+    match name
+        .strip_prefix("https://w3id.org/security#")
+        .unwrap_or(&name)
+    {
+        "GCA2015" | "URDNA2015" | "RDFC-1.0" => Ok(()),
+        _ => Err((*name).into()),
+    }
+}
+
 fn try_json_ld_qs(
     qs: JsonLdQuadSource,
 ) -> Result<
@@ -536,37 +1192,50 @@ fn try_json_ld_qs(
 mod tests {
     use std::sync::Arc;
 
+    use base64::Engine as _;
     use futures_executor::block_on;
-    use json_syntax::Parse as _;
+    use json_syntax::{Parse as _, Print as _};
     use sophia_inmem::dataset::LightDataset;
     use sophia_iri::Iri;
 
-    use crate::util::test::parse_nq;
+    use crate::util::test::{parse_nq, rsa_private_key};
 
     use super::*;
 
-    #[test]
-    fn it_works() {
-        const DOCUMENT: &str = r#"
-            {
-                "@context": [
-                    "https://w3id.org/security/v1",
-                    {
-                        "content": "https://www.w3.org/ns/activitystreams#content"
-                    }
-                ],
-                "type": "https://www.w3.org/ns/activitystreams#Note",
-                "content": "Hello, world!",
-                "signature": {
-                    "@context": "https://w3id.org/identity/v1",
-                    "type": "RsaSignature2017",
-                    "created": "2024-01-01T00:00:00Z",
-                    "creator": "https://example.com/#me",
-                    "nonce": "deadbeef12345678",
-                    "signatureValue": "EuukoY4e2Bdp18mQov48Q1E38XetV03SI+DHJOdFm/t8Cz+WP8qbgtM8fg0L9J15B8yyZ7J2+nSeqi2oAuuo7g=="
+    /// A document signed with `rsa_private_key`.
+    const DOCUMENT: &str = r#"
+        {
+            "@context": [
+                "https://w3id.org/security/v1",
+                {
+                    "content": "https://www.w3.org/ns/activitystreams#content"
                 }
+            ],
+            "type": "https://www.w3.org/ns/activitystreams#Note",
+            "content": "Hello, world!",
+            "signature": {
+                "@context": "https://w3id.org/identity/v1",
+                "type": "RsaSignature2017",
+                "created": "2024-01-01T00:00:00Z",
+                "creator": "https://example.com/#me",
+                "nonce": "deadbeef12345678",
+                "signatureValue": "EuukoY4e2Bdp18mQov48Q1E38XetV03SI+DHJOdFm/t8Cz+WP8qbgtM8fg0L9J15B8yyZ7J2+nSeqi2oAuuo7g=="
             }
-        "#;
+        }
+    "#;
+    const SIGNATURE_VALUE: &str =
+        "EuukoY4e2Bdp18mQov48Q1E38XetV03SI+DHJOdFm/t8Cz+WP8qbgtM8fg0L9J15B8yyZ7J2+nSeqi2oAuuo7g==";
+
+    fn load_doc(json: &str) -> RemoteDocument<ArcIri> {
+        let json = json_syntax::Value::parse_str(json, |span| {
+            locspan::Location::new(Iri::new_unchecked(Arc::from(PLACEHOLDER_IRI)), span)
+        })
+        .unwrap();
+        RemoteDocument::new(None, None, json)
+    }
+
+    #[test]
+    fn it_works() {
         const DATASET: &str = r#"
             _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
             _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
@@ -577,19 +1246,14 @@ mod tests {
             _:b0 <https://w3id.org/security#nonce> "deadbeef12345678" .
         "#;
 
-        let placeholder_iri = Iri::new_unchecked(Arc::from("urn:x-placeholder"));
-        let document = RemoteDocument::new(
-            None,
-            None,
-            json_syntax::Value::parse_str(DOCUMENT, |span| {
-                locspan::Location::new(placeholder_iri.clone(), span)
-            })
-            .unwrap(),
-        );
         let SignedDocument {
             document,
             signatures,
-        } = block_on(SignedDocument::<LightDataset>::parse(document)).unwrap();
+            ..
+        } = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            DOCUMENT, None,
+        ))
+        .unwrap();
 
         assert_eq_dataset!(document, parse_nq(DATASET));
 
@@ -597,6 +1261,1355 @@ mod tests {
         assert_eq_dataset!(signature.options, parse_nq(OPTIONS));
         assert_eq!(signature.id, None);
         assert_eq!(signature.kind, SignatureType::RsaSignature2017);
-        assert_eq!(signature.signature_value, base64::engine::general_purpose::STANDARD.decode("EuukoY4e2Bdp18mQov48Q1E38XetV03SI+DHJOdFm/t8Cz+WP8qbgtM8fg0L9J15B8yyZ7J2+nSeqi2oAuuo7g==").unwrap());
+        assert_eq!(
+            signature.signature_value,
+            base64::engine::general_purpose::STANDARD
+                .decode(SIGNATURE_VALUE)
+                .unwrap()
+        );
+        signature
+            .verify(&document, &rsa_private_key().to_public_key())
+            .unwrap();
+    }
+
+    #[test]
+    fn signature_type() {
+        type E = <LightDataset as Dataset>::Error;
+
+        fn parse(document: &str) -> Result<SignedDocument<LightDataset>, Error<E, E>> {
+            block_on(SignedDocument::<LightDataset>::parse_json_str(
+                document, None,
+            ))
+        }
+
+        for ty in [
+            r#""sec:RsaSignature2017""#,
+            r#""https://w3id.org/security#RsaSignature2017""#,
+            r#"["GraphSignature2012", "RsaSignature2017"]"#,
+        ] {
+            let document = parse(&DOCUMENT.replace(r#""RsaSignature2017""#, ty)).unwrap();
+            let [signature] = document.signatures() else {
+                panic!("expected exactly one signature");
+            };
+            assert_eq!(signature.kind, SignatureType::RsaSignature2017);
+            document
+                .verify_rsa_signature_2017(&rsa_private_key().to_public_key())
+                .unwrap();
+        }
+
+        for ty in [r#""GraphSignature2012""#, r#"["GraphSignature2012"]"#, "42"] {
+            let e = parse(&DOCUMENT.replace(r#""RsaSignature2017""#, ty)).unwrap_err();
+            assert!(matches!(e, Error::UnsupportedType), "{:?}", e);
+        }
+        let e = parse(&DOCUMENT.replace(r#""type": "RsaSignature2017","#, "")).unwrap_err();
+        assert!(matches!(e, Error::UnsupportedType), "{:?}", e);
+    }
+
+    #[test]
+    fn signature_key() {
+        let proof = DOCUMENT.replace(r#""signature""#, r#""proof""#);
+
+        type E = <LightDataset as Dataset>::Error;
+
+        fn parse(
+            parser: &DocumentParser,
+            json: &str,
+        ) -> Result<SignedDocument<LightDataset>, Error<E, E>> {
+            block_on(parser.parse(load_doc(json)))
+        }
+
+        let e = parse(&DocumentParser::new(), &proof).unwrap_err();
+        assert!(matches!(e, Error::UnsupportedProofFormat), "{:?}", e);
+
+        let parser = DocumentParser::new().signature_key("proof");
+        parse(&parser, &proof)
+            .unwrap()
+            .verify_rsa_signature_2017(&rsa_private_key().to_public_key())
+            .unwrap();
+        let e = parse(&parser, DOCUMENT).unwrap_err();
+        assert!(matches!(e, Error::MissingSignatureOptions), "{:?}", e);
+    }
+
+    #[test]
+    fn debug() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v1",
+                "signature": {
+                    "type": "RsaSignature2017",
+                    "created": "2024-01-01T00:00:00Z",
+                    "creator": "https://example.com/#me",
+                    "signatureValue": "3q2+7w=="
+                }
+            }
+        "#;
+
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            DOCUMENT, None,
+        ))
+        .unwrap();
+        assert_eq!(
+            format!("{:?}", document.signatures()[0]),
+            concat!(
+                r#"Signature { id: None, kind: RsaSignature2017, "#,
+                r#"created: Some("2024-01-01T00:00:00Z"), creator: Some("https://example.com/#me"), "#,
+                r#"signature_value: "3q2+7w==", .. }"#,
+            ),
+        );
+    }
+
+    #[test]
+    fn multiple_signatures() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": [
+                    "https://w3id.org/security/v1",
+                    {
+                        "content": "https://www.w3.org/ns/activitystreams#content"
+                    }
+                ],
+                "type": "https://www.w3.org/ns/activitystreams#Note",
+                "content": "Hello, world!",
+                "signature": [
+                    {
+                        "type": "RsaSignature2017",
+                        "created": "2024-01-01T00:00:00Z",
+                        "creator": "https://example.com/#me",
+                        "nonce": "deadbeef12345678",
+                        "signatureValue": "EuukoY4e2Bdp18mQov48Q1E38XetV03SI+DHJOdFm/t8Cz+WP8qbgtM8fg0L9J15B8yyZ7J2+nSeqi2oAuuo7g=="
+                    },
+                    {
+                        "type": "RsaSignature2017",
+                        "created": "2024-01-01T00:00:00Z",
+                        "creator": "https://example.com/#me",
+                        "nonce": "deadbeef12345678",
+                        "signatureValue": "SIGNATURE"
+                    }
+                ]
+            }
+        "#;
+
+        let key = rsa_private_key().to_public_key();
+
+        let valid = DOCUMENT.replace("SIGNATURE", SIGNATURE_VALUE);
+        let document =
+            block_on(SignedDocument::<LightDataset>::parse_json_str(&valid, None)).unwrap();
+        assert_eq!(document.signatures().len(), 2);
+        document.verify_rsa_signature_2017(&key).unwrap();
+        #[cfg(feature = "rayon")]
+        document.verify_all_parallel(&key).unwrap();
+
+        let invalid = DOCUMENT.replace(
+            "SIGNATURE",
+            &base64::engine::general_purpose::STANDARD.encode([0; 64]),
+        );
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &invalid, None,
+        ))
+        .unwrap();
+        assert!(matches!(
+            document.verify_rsa_signature_2017(&key),
+            Err(verify::Error::Verification(_))
+        ));
+        #[cfg(feature = "rayon")]
+        assert!(matches!(
+            document.verify_all_parallel(&key),
+            Err(verify::Error::Verification(_))
+        ));
+    }
+
+    #[test]
+    fn base64url_signature_value() {
+        let base64url = SIGNATURE_VALUE
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_");
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &DOCUMENT.replace(SIGNATURE_VALUE, &base64url),
+            None,
+        ))
+        .unwrap();
+        document
+            .verify_rsa_signature_2017(&rsa_private_key().to_public_key())
+            .unwrap();
+    }
+
+    #[test]
+    fn proof() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v2",
+                "proof": {
+                    "type": "RsaSignature2018",
+                    "created": "2024-01-01T00:00:00Z",
+                    "verificationMethod": "https://example.com/#me",
+                    "proofPurpose": "assertionMethod",
+                    "jws": "eyJhbGciOiJSUzI1NiIsImI2NCI6ZmFsc2UsImNyaXQiOlsiYjY0Il19..AAAA"
+                }
+            }
+        "#;
+
+        let e = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            DOCUMENT, None,
+        ))
+        .unwrap_err();
+        assert!(matches!(e, Error::UnsupportedProofFormat));
+
+        let e = block_on(SignedDocument::<LightDataset>::parse_json_str("{}", None)).unwrap_err();
+        assert!(matches!(e, Error::MissingSignatureOptions));
+    }
+
+    #[test]
+    fn error_eq() {
+        type E = Error<(), ()>;
+
+        assert_eq!(E::BadSubject, E::BadSubject);
+        assert_ne!(E::BadSubject, E::NestingSignatureNode);
+        assert_eq!(
+            E::Verify(verify::Error::Verification(rsa::Error::Verification)),
+            E::Verify(verify::Error::Verification(rsa::Error::Verification)),
+        );
+        assert_ne!(
+            E::Verify(verify::Error::SignatureLengthMismatch {
+                expected: 64,
+                actual: 3
+            }),
+            E::Verify(verify::Error::SignatureLengthMismatch {
+                expected: 64,
+                actual: 4
+            }),
+        );
+        // Only the variants are compared for the errors from the JSON-LD processor.
+        assert_eq!(
+            E::Document(JsonLdError::ExpandError("a".into())),
+            E::Document(JsonLdError::ExpandError("b".into())),
+        );
+        assert_ne!(
+            E::Document(JsonLdError::ExpandError("a".into())),
+            E::Options(JsonLdError::ExpandError("a".into())),
+        );
+    }
+
+    #[test]
+    fn signature_subject() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v1",
+                "type": "https://www.w3.org/ns/activitystreams#Note",
+                "signature": {
+                    "@context": "https://w3id.org/identity/v1",
+                    "type": "RsaSignature2017",
+                    "created": "2024-01-01T00:00:00Z",
+                    "creator": "https://example.com/#me",
+                    "signatureValue": "AAAA"
+                }
+            }
+        "#;
+
+        type E = <LightDataset as Dataset>::Error;
+
+        fn parse(json: &str) -> Result<(), Error<E, E>> {
+            block_on(SignedDocument::<LightDataset>::parse_json_str(json, None)).map(|_| ())
+        }
+
+        parse(DOCUMENT).unwrap();
+
+        let document = DOCUMENT.replace(
+            r#""type": "RsaSignature2017","#,
+            r#""type": "RsaSignature2017", "@id": "https://example.com/#sig","#,
+        );
+        assert!(matches!(parse(&document), Err(Error::BadSubject)));
+
+        let document = DOCUMENT.replace(
+            r#""creator": "https://example.com/#me","#,
+            r#""creator": { "@id": "https://example.com/#me", "domain": "example.com" },"#,
+        );
+        assert!(matches!(parse(&document), Err(Error::NestingSignatureNode)));
+
+        let document = DOCUMENT.replace(
+            r#""creator": "https://example.com/#me","#,
+            r#""creator": { "domain": "example.com" },"#,
+        );
+        assert!(matches!(parse(&document), Err(Error::NestingSignatureNode)));
+
+        let document = DOCUMENT.replace(
+            r#""created": "2024-01-01T00:00:00Z","#,
+            r#""@graph": { "domain": "example.com" }, "created": "2024-01-01T00:00:00Z","#,
+        );
+        assert!(matches!(parse(&document), Err(Error::NestingSignatureNode)));
+    }
+
+    #[test]
+    fn error_conversion() {
+        type E = <LightDataset as Dataset>::Error;
+
+        async fn parse_and_verify(json: &str) -> Result<(), Error<E, E>> {
+            let document = SignedDocument::<LightDataset>::parse_json_str(json, None).await?;
+            document.verify_rsa_signature_2017(&rsa_private_key().to_public_key())?;
+            Ok(())
+        }
+
+        let e = block_on(parse_and_verify(&DOCUMENT.replace(SIGNATURE_VALUE, "AAAA"))).unwrap_err();
+        assert!(matches!(
+            e,
+            Error::Verify(verify::Error::SignatureLengthMismatch {
+                expected: 64,
+                actual: 3
+            })
+        ));
+
+        let document = DOCUMENT.replace(SIGNATURE_VALUE, &format!("{}==", "A".repeat(86)));
+        let e = block_on(parse_and_verify(&document)).unwrap_err();
+        assert!(matches!(e, Error::Verify(verify::Error::Verification(_))));
+
+        let document = DOCUMENT.replace(SIGNATURE_VALUE, "");
+        let e = block_on(parse_and_verify(&document)).unwrap_err();
+        assert!(matches!(e, Error::BadSignatureValue));
+    }
+
+    #[test]
+    fn created_lenient() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": {
+                    "content": "https://www.w3.org/ns/activitystreams#content"
+                },
+                "content": "Hello, world!",
+                "signature": {
+                    "@context": {
+                        "created": "http://purl.org/dc/terms/created",
+                        "creator": { "@id": "http://purl.org/dc/terms/creator", "@type": "@id" }
+                    },
+                    "type": "RsaSignature2017",
+                    "created": "2024-01-01T00:00:00Z",
+                    "creator": "https://example.com/#me",
+                    "signatureValue": "AA=="
+                }
+            }
+        "#;
+
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            DOCUMENT, None,
+        ))
+        .unwrap();
+        let [signature] = document.signatures() else {
+            panic!("expected exactly one signature");
+        };
+        assert!(signature.created().is_none());
+        let created = signature.created_lenient().unwrap().unwrap();
+        assert_eq!(&*created.value, "2024-01-01T00:00:00Z");
+        assert!(created.untyped);
+    }
+
+    #[test]
+    fn other_options() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v1",
+                "signature": {
+                    "type": "RsaSignature2017",
+                    "created": "2024-01-01T00:00:00Z",
+                    "creator": "https://example.com/#me",
+                    "owner": "https://example.com/",
+                    "signatureValue": "AA=="
+                }
+            }
+        "#;
+
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            DOCUMENT, None,
+        ))
+        .unwrap();
+
+        let [signature] = document.signatures() else {
+            panic!("expected exactly one signature");
+        };
+        let creators = document.creators().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            creators,
+            [IriRef::new_unchecked("https://example.com/#me".into())]
+        );
+        let others = signature
+            .other_options()
+            .map(|q| q.unwrap().to_spog())
+            .collect::<Vec<_>>();
+        let [([_, p, o], None)] = &others[..] else {
+            panic!("unexpected options: {:?}", others);
+        };
+        assert!(Term::eq(
+            p,
+            Iri::new_unchecked("https://w3id.org/security#owner")
+        ));
+        assert!(Term::eq(o, Iri::new_unchecked("https://example.com/")));
+    }
+
+    #[test]
+    fn merge_context() {
+        fn merge(dc: &str, oc: &str) -> String {
+            let dc = json_syntax::Value::parse_str(dc, |span| span).unwrap();
+            let mut oc = json_syntax::Value::parse_str(oc, |span| span).unwrap();
+            super::merge_context(&dc, &mut oc);
+            oc.compact_print().to_string()
+        }
+
+        assert_eq!(
+            merge(
+                r#"{"a":"https://example.com/a"}"#,
+                r#"{"b":"https://example.com/b"}"#
+            ),
+            r#"[{"a":"https://example.com/a"},{"b":"https://example.com/b"}]"#,
+        );
+        assert_eq!(
+            merge(
+                r#"{"a":"https://example.com/a"}"#,
+                r#"["https://w3id.org/identity/v1",{"a":"https://example.com/a"}]"#,
+            ),
+            r#"["https://w3id.org/identity/v1",{"a":"https://example.com/a"}]"#,
+        );
+        assert_eq!(
+            merge(
+                r#"["https://w3id.org/security/v1",{"a":"https://example.com/a"}]"#,
+                r#""https://w3id.org/security/v1""#,
+            ),
+            r#"[{"a":"https://example.com/a"},"https://w3id.org/security/v1"]"#,
+        );
+        assert_eq!(
+            merge(
+                r#""https://w3id.org/security/v1""#,
+                r#"[{"b":"https://example.com/b"},"https://w3id.org/security/v1"]"#,
+            ),
+            r#"[{"b":"https://example.com/b"},"https://w3id.org/security/v1"]"#,
+        );
+
+        // A quadratic implementation would take billions of comparisons here.
+        const N: usize = 50_000;
+        let dc = json_syntax::Value::Array(
+            (0..N)
+                .map(|i| locspan::Meta(format!("https://example.com/{}", i).into(), ()))
+                .collect(),
+        );
+        let dc = locspan::Meta(dc, ());
+        let mut oc = locspan::Meta(
+            json_syntax::Value::Array(
+                (N / 2..N + N / 2)
+                    .map(|i| locspan::Meta(format!("https://example.com/{}", i).into(), ()))
+                    .collect(),
+            ),
+            (),
+        );
+        super::merge_context(&dc, &mut oc);
+        let merged = oc.as_array().unwrap();
+        assert_eq!(merged.len(), N + N / 2);
+        for (i, c) in merged.iter().enumerate() {
+            assert_eq!(c.as_str(), Some(&*format!("https://example.com/{}", i)));
+        }
+    }
+
+    #[test]
+    fn verify_resolved() {
+        let resolver = |creator: IriRef<&str>| {
+            future::ready(if creator.as_str() == "https://example.com/#me" {
+                Ok(rsa_private_key().to_public_key())
+            } else {
+                Err("unknown creator")
+            })
+        };
+        let document = block_on(
+            <DocumentParser>::new()
+                .with_key_resolver(resolver)
+                .parse::<LightDataset, LightDataset>(load_doc(DOCUMENT)),
+        )
+        .unwrap();
+
+        fn assert_send<T: Send>(t: T) -> T {
+            t
+        }
+        block_on(assert_send(document.verify_resolved())).unwrap();
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn verification_method() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": [
+                    "https://w3id.org/security/v1",
+                    {
+                        "content": "https://www.w3.org/ns/activitystreams#content"
+                    }
+                ],
+                "type": "https://www.w3.org/ns/activitystreams#Note",
+                "content": "Hello, world!"
+            }
+        "#;
+        const DATASET: &str = r#"
+            _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let key = rsa_private_key();
+        let signature = <crate::SignOptions<'_, '_>>::new()
+            .created("2024-01-01T00:00:00Z")
+            .deterministic([0; 32])
+            .creator_key(crate::CreatorKey::VerificationMethod)
+            .sign_rsa_signature_2017(
+                &parse_nq(DATASET),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let value = json_syntax::Value::parse_str(DOCUMENT, |_| ()).unwrap();
+        let signed = json_syntax::to_value(crate::serde::InsertSignature::new(
+            value.into_value(),
+            &signature,
+        ))
+        .unwrap();
+        let signed = signed.compact_print().to_string();
+        assert!(signed.contains(r#""verificationMethod":"https://example.com/#me""#));
+        assert!(!signed.contains(r#""creator""#));
+
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &signed, None,
+        ))
+        .unwrap();
+        let [ref parsed] = *document.signatures() else {
+            panic!("expected exactly one signature");
+        };
+        assert!(parsed.creator().is_none());
+        assert_eq!(
+            parsed.verification_method().unwrap().unwrap().as_str(),
+            "https://example.com/#me",
+        );
+        assert_eq!(parsed.other_options().count(), 0);
+        document
+            .verify_rsa_signature_2017(&key.to_public_key())
+            .unwrap();
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn http_context() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": [
+                    "https://w3id.org/security/v1",
+                    {
+                        "content": "https://www.w3.org/ns/activitystreams#content"
+                    }
+                ],
+                "type": "https://www.w3.org/ns/activitystreams#Note",
+                "content": "Hello, world!"
+            }
+        "#;
+        const DATASET: &str = r#"
+            _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let key = rsa_private_key();
+        let signature = <crate::SignOptions<'_, '_>>::new()
+            .created("2024-01-01T00:00:00Z")
+            .deterministic([0; 32])
+            .sign_rsa_signature_2017(
+                &parse_nq(DATASET),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let value = json_syntax::Value::parse_str(DOCUMENT, |_| ()).unwrap();
+        let signed = json_syntax::to_value(crate::serde::InsertSignature::new(
+            value.into_value(),
+            &signature,
+        ))
+        .unwrap()
+        .compact_print()
+        .to_string();
+
+        // Both the document and the signature options declare the plain `http` variant, which must
+        // be resolved from the preloaded contexts without going online.
+        for context in ["http://w3id.org/security/v1", "http://w3id.org/identity/v1"] {
+            let signed = signed.replace("https://w3id.org/security/v1", context);
+            assert_eq!(signed.matches(context).count(), 2);
+            let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+                &signed, None,
+            ))
+            .unwrap();
+            document
+                .verify_rsa_signature_2017(&key.to_public_key())
+                .unwrap();
+        }
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn preloaded_contexts() {
+        const DATASET: &str = r#"
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let key = rsa_private_key();
+        let signature = <crate::SignOptions<'_, '_>>::new()
+            .sign_rsa_signature_2017(
+                &parse_nq(DATASET),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+        let parse = |context: &str| {
+            let document = format!(r#"{{"@context": {}, "content": "Hello, world!"}}"#, context);
+            let value = json_syntax::Value::parse_str(&document, |_| ()).unwrap();
+            let signed = json_syntax::to_value(crate::serde::InsertSignature::new(
+                value.into_value(),
+                &signature,
+            ))
+            .unwrap()
+            .compact_print()
+            .to_string();
+            block_on(SignedDocument::<LightDataset>::parse_json_str(
+                &signed, None,
+            ))
+            .unwrap()
+        };
+
+        let document = parse(
+            r#"[
+                "https://w3id.org/identity/v1",
+                "https://w3id.org/security/v1",
+                { "content": "https://www.w3.org/ns/activitystreams#content" }
+            ]"#,
+        );
+        assert_eq!(
+            document.preloaded_contexts(),
+            [
+                Iri::new_unchecked("https://w3id.org/identity/v1".into()),
+                Iri::new_unchecked("https://w3id.org/security/v1".into()),
+            ],
+        );
+        document
+            .verify_rsa_signature_2017(&key.to_public_key())
+            .unwrap();
+
+        // The serialized signature declares the Security Vocabulary context by itself.
+        let document = parse(
+            r#"{
+                "content": "https://www.w3.org/ns/activitystreams#content",
+                "sec": "https://w3id.org/security#"
+            }"#,
+        );
+        assert_eq!(
+            document.preloaded_contexts(),
+            [Iri::new_unchecked("https://w3id.org/security/v1".into())],
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn semantically_eq() {
+        const DATASET: &str = r#"
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let key = rsa_private_key();
+        let sign = |nonce| {
+            <crate::SignOptions<'_, '_>>::new()
+                .created("2024-01-01T00:00:00Z")
+                .nonce(Some(nonce))
+                .sign_rsa_signature_2017(
+                    &parse_nq(DATASET),
+                    &key,
+                    Iri::new_unchecked("https://example.com/#me"),
+                )
+                .unwrap()
+        };
+        let original = sign("deadbeef12345678");
+
+        let value = json_syntax::Value::parse_str(
+            r#"{
+                "@context": { "content": "https://www.w3.org/ns/activitystreams#content" },
+                "content": "Hello, world!"
+            }"#,
+            |_| (),
+        )
+        .unwrap();
+        let signed = json_syntax::to_value(crate::serde::InsertSignature::new(
+            value.into_value(),
+            &original,
+        ))
+        .unwrap()
+        .compact_print()
+        .to_string();
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &signed, None,
+        ))
+        .unwrap();
+        let [parsed] = document.signatures() else {
+            panic!("expected a single signature");
+        };
+
+        assert!(parsed.semantically_eq(&original).unwrap());
+        assert!(!parsed.semantically_eq(&sign("0123456789abcdef")).unwrap());
+        let mut tampered = sign("deadbeef12345678");
+        tampered.signature_value[0] ^= 1;
+        assert!(!parsed.semantically_eq(&tampered).unwrap());
+    }
+
+    #[test]
+    fn iri_domain() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v1",
+                "signature": {
+                    "type": "RsaSignature2017",
+                    "created": "2024-01-01T00:00:00Z",
+                    "creator": "https://example.com/#me",
+                    "domain": { "@id": "https://example.com/scope" },
+                    "signatureValue": "3q2+7w=="
+                }
+            }
+        "#;
+
+        let domain = |document: &str| {
+            let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+                document, None,
+            ))
+            .unwrap();
+            document.signatures()[0].domain().transpose().unwrap()
+        };
+
+        assert_eq!(
+            domain(DOCUMENT).as_deref(),
+            Some("https://example.com/scope")
+        );
+        let literal = DOCUMENT.replace(
+            r#"{ "@id": "https://example.com/scope" }"#,
+            r#""example.com""#,
+        );
+        assert_eq!(domain(&literal).as_deref(), Some("example.com"));
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn to_signature() {
+        use sophia_api::term::SimpleTerm;
+
+        const DATASET: &str = r#"
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let key = rsa_private_key();
+        let original = <crate::SignOptions<'_, '_>>::new()
+            .created("2024-01-01T00:00:00Z")
+            .expires_in(std::time::Duration::from_secs(60))
+            .domain("example.com")
+            .nonce(Some("deadbeef12345678"))
+            .creator_key(crate::CreatorKey::VerificationMethod)
+            .extra_option(
+                Iri::new_unchecked("https://example.com/ns#purpose"),
+                SimpleTerm::Iri(IriRef::new_unchecked("https://example.com/ns#test".into())),
+            )
+            .sign_rsa_signature_2017(
+                &parse_nq(DATASET),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let value = json_syntax::Value::parse_str(
+            r#"{
+                "@context": { "content": "https://www.w3.org/ns/activitystreams#content" },
+                "content": "Hello, world!"
+            }"#,
+            |_| (),
+        )
+        .unwrap();
+        let signed = json_syntax::to_value(crate::serde::InsertSignature::new(
+            value.into_value(),
+            &original,
+        ))
+        .unwrap()
+        .compact_print()
+        .to_string();
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &signed, None,
+        ))
+        .unwrap();
+        let [parsed] = document.signatures() else {
+            panic!("expected a single signature");
+        };
+
+        let signature = parsed.to_signature().unwrap().unwrap();
+        assert_eq!(signature.created, original.created);
+        assert_eq!(signature.expires, original.expires);
+        assert_eq!(signature.creator, original.creator);
+        assert_eq!(
+            signature.creator_key(),
+            crate::CreatorKey::VerificationMethod
+        );
+        assert_eq!(signature.domain, original.domain);
+        assert_eq!(signature.nonce, original.nonce);
+        assert!(parsed.semantically_eq(&signature).unwrap());
+        crate::verify_rsa_signature_2017(
+            document.document(),
+            &signature.options_dataset(None),
+            &key.to_public_key(),
+            &signature.signature_value,
+        )
+        .unwrap();
+
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            r#"{
+                "@context": "https://w3id.org/security/v1",
+                "signature": {
+                    "type": "RsaSignature2017",
+                    "creator": "https://example.com/#me",
+                    "signatureValue": "AA=="
+                }
+            }"#,
+            None,
+        ))
+        .unwrap();
+        assert!(document.signatures()[0].to_signature().unwrap().is_none());
+    }
+
+    #[test]
+    fn remove_signature_entries() {
+        let mut document = json_syntax::Value::parse_str(
+            r#"{
+                "signature": 1,
+                "content": { "signature": 2 },
+                "signature": 3
+            }"#,
+            |_| (),
+        )
+        .unwrap()
+        .into_value();
+        assert_eq!(super::remove_signature_entries(&mut document), 2);
+        assert_eq!(
+            document.compact_print().to_string(),
+            r#"{"content":{"signature":2}}"#,
+        );
+        assert_eq!(super::remove_signature_entries(&mut document), 0);
+        assert_eq!(
+            super::remove_signature_entries(&mut json_syntax::Value::<()>::Null),
+            0,
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn write_signed_document_to() {
+        const DOCUMENT: &str = r#"{
+            "@context": { "content": "https://www.w3.org/ns/activitystreams#content" },
+            "content": "Hello, \"world\"!",
+            "signature": "stale"
+        }"#;
+
+        let key = rsa_private_key();
+        let document = json_syntax::Value::parse_str(DOCUMENT, |_| ())
+            .unwrap()
+            .into_value();
+        let mut unsigned = document.clone();
+        unsigned.as_object_mut().unwrap().remove("signature");
+        let signature = <crate::SignOptions<'_, '_>>::new()
+            .sign_rsa_signature_2017(
+                &parse_nq(
+                    r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, \"world\"!" ."#,
+                ),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let mut output = Vec::new();
+        signature
+            .write_signed_document_to(&document, &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output,
+            json_syntax::to_value(crate::serde::InsertSignature::new(&unsigned, &signature))
+                .unwrap()
+                .compact_print()
+                .to_string(),
+        );
+        block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &output, None,
+        ))
+        .unwrap()
+        .verify_rsa_signature_2017(&key.to_public_key())
+        .unwrap();
+
+        let e = signature
+            .write_signed_document_to(&json_syntax::Value::<()>::Null, Vec::new())
+            .unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn created_nanoseconds() {
+        const CREATED: &str = "2024-01-01T00:00:00.123456789Z";
+
+        let key = rsa_private_key();
+        let document = json_syntax::Value::parse_str(
+            r#"{
+                "@context": { "content": "https://www.w3.org/ns/activitystreams#content" },
+                "content": "Hello, world!"
+            }"#,
+            |_| (),
+        )
+        .unwrap()
+        .into_value();
+        let signature = <crate::SignOptions<'_, '_>>::new()
+            .created(CREATED)
+            .sign_rsa_signature_2017(
+                &parse_nq(
+                    r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" ."#,
+                ),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let mut output = Vec::new();
+        signature
+            .write_signed_document_to(&document, &mut output)
+            .unwrap();
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            core::str::from_utf8(&output).unwrap(),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(
+            &*document.signatures()[0].created().unwrap().unwrap(),
+            CREATED
+        );
+        document
+            .verify_rsa_signature_2017(&key.to_public_key())
+            .unwrap();
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn context_choice() {
+        let key = rsa_private_key();
+        let document = json_syntax::Value::parse_str(
+            r#"{
+                "@context": { "content": "https://www.w3.org/ns/activitystreams#content" },
+                "content": "Hello, world!"
+            }"#,
+            |_| (),
+        )
+        .unwrap()
+        .into_value();
+
+        for context in [
+            crate::ContextChoice::SecurityV1,
+            crate::ContextChoice::IdentityV1,
+        ] {
+            for creator_key in [
+                crate::CreatorKey::Creator,
+                crate::CreatorKey::VerificationMethod,
+            ] {
+                let signature = <crate::SignOptions<'_, '_>>::new()
+                    .context(context.clone())
+                    .creator_key(creator_key)
+                    .expires_in(core::time::Duration::from_secs(60))
+                    .domain("example.com")
+                    .sign_rsa_signature_2017(
+                        &parse_nq(
+                            r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" ."#,
+                        ),
+                        &key,
+                        Iri::new_unchecked("https://example.com/#me"),
+                    )
+                    .unwrap();
+
+                let mut output = Vec::new();
+                signature
+                    .write_signed_document_to(&document, &mut output)
+                    .unwrap();
+                let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+                    core::str::from_utf8(&output).unwrap(),
+                    None,
+                ))
+                .unwrap();
+                assert!(document.signatures()[0]
+                    .semantically_eq(&signature)
+                    .unwrap());
+                document
+                    .verify_rsa_signature_2017(&key.to_public_key())
+                    .unwrap();
+            }
+        }
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn multiple_graphs() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": [
+                    "https://w3id.org/security/v1",
+                    {
+                        "content": "https://www.w3.org/ns/activitystreams#content"
+                    }
+                ],
+                "@id": "https://example.com/g",
+                "content": "Hello, world!",
+                "@graph": {
+                    "@id": "https://example.com/g",
+                    "content": "Hello, world!"
+                }
+            }
+        "#;
+        const DATASET: &str = r#"
+            <https://example.com/g> <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+            <https://example.com/g> <https://www.w3.org/ns/activitystreams#content> "Hello, world!" <https://example.com/g> .
+        "#;
+
+        let key = rsa_private_key();
+        let dataset = parse_nq(DATASET);
+        let signature = <crate::SignOptions<'_, '_>>::new()
+            .created("2024-01-01T00:00:00Z")
+            .deterministic([0; 32])
+            .sign_rsa_signature_2017(
+                &dataset,
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let value = json_syntax::Value::parse_str(DOCUMENT, |_| ()).unwrap();
+        let signed = json_syntax::to_value(crate::serde::InsertSignature::new(
+            value.into_value(),
+            &signature,
+        ))
+        .unwrap()
+        .compact_print()
+        .to_string();
+
+        // The triple is kept in both of the graphs.
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &signed, None,
+        ))
+        .unwrap();
+        assert_eq!(document.document().quads().count(), 2);
+        assert_eq!(
+            crate::hash_dataset_hex(document.document()).unwrap(),
+            crate::hash_dataset_hex(&dataset).unwrap(),
+        );
+        document
+            .verify_rsa_signature_2017(&key.to_public_key())
+            .unwrap();
+
+        // Tampering with the named graph alone invalidates the signature.
+        let (head, tail) = signed.split_at(signed.rfind("Hello, world!").unwrap());
+        let tampered = format!("{}{}", head, tail.replacen("world", "graph", 1));
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &tampered, None,
+        ))
+        .unwrap();
+        let e = document
+            .verify_rsa_signature_2017(&key.to_public_key())
+            .unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+    }
+
+    #[cfg(all(feature = "serde", feature = "sign", feature = "thread-rng"))]
+    #[test]
+    fn extra_options() {
+        use sophia_api::term::SimpleTerm;
+
+        const DOCUMENT: &str = r#"
+            {
+                "@context": {
+                    "content": "https://www.w3.org/ns/activitystreams#content"
+                },
+                "content": "Hello, world!"
+            }
+        "#;
+        const DATASET: &str = r#"
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let key = rsa_private_key();
+        let signature = <crate::SignOptions<'_, '_>>::new()
+            .extra_option(
+                Iri::new_unchecked("https://example.com/ns#purpose"),
+                SimpleTerm::Iri(IriRef::new_unchecked("https://example.com/ns#test".into())),
+            )
+            .extra_option(
+                Iri::new_unchecked("https://example.com/ns#note"),
+                SimpleTerm::LiteralDatatype("custom".into(), IriRef::new_unchecked(consts::STRING)),
+            )
+            .extra_option(
+                Iri::new_unchecked("https://example.com/ns#count"),
+                SimpleTerm::LiteralDatatype(
+                    "42".into(),
+                    IriRef::new_unchecked("http://www.w3.org/2001/XMLSchema#integer".into()),
+                ),
+            )
+            .sign_rsa_signature_2017(
+                &parse_nq(DATASET),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let value = json_syntax::Value::parse_str(DOCUMENT, |_| ()).unwrap();
+        let signed = json_syntax::to_value(crate::serde::InsertSignature::new(
+            value.into_value(),
+            &signature,
+        ))
+        .unwrap()
+        .compact_print()
+        .to_string();
+
+        let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &signed, None,
+        ))
+        .unwrap();
+        let [ref parsed] = *document.signatures() else {
+            panic!("expected exactly one signature");
+        };
+        assert_eq!(parsed.other_options().count(), 3);
+        document
+            .verify_rsa_signature_2017(&key.to_public_key())
+            .unwrap();
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send<T: Send>(_: &T) {}
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<SignedDocument<LightDataset>>();
+        assert_send_sync::<Signature<LightDataset>>();
+        assert_send_sync::<Error<Infallible, Infallible>>();
+
+        // The futures need to be `Send` to be spawned on multi-threaded executors.
+        let parser = <DocumentParser>::new();
+        assert_send(&parser.parse::<LightDataset, LightDataset>(load_doc("{}")));
+        assert_send(&SignedDocument::<LightDataset>::parse_json_str("{}", None));
+    }
+
+    #[test]
+    fn immutable_options_dataset() {
+        use sophia_api::dataset::DQuadSource;
+        use sophia_api::source::{QuadSource, StreamResult};
+
+        /// A dataset that can be collected but not mutated.
+        struct Frozen(LightDataset);
+
+        impl Dataset for Frozen {
+            type Quad<'x> = <LightDataset as Dataset>::Quad<'x>;
+            type Error = <LightDataset as Dataset>::Error;
+
+            fn quads(&self) -> DQuadSource<'_, Self> {
+                self.0.quads()
+            }
+        }
+
+        impl CollectibleDataset for Frozen {
+            fn from_quad_source<QS: QuadSource>(
+                quads: QS,
+            ) -> StreamResult<Self, QS::Error, Self::Error> {
+                LightDataset::from_quad_source(quads).map(Frozen)
+            }
+        }
+
+        impl SetDataset for Frozen {}
+
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v1",
+                "https://www.w3.org/ns/activitystreams#content": "Hello, world!",
+                "signature": {
+                    "type": "RsaSignature2017",
+                    "created": "2024-01-01T00:00:00Z",
+                    "creator": "https://example.com/#me",
+                    "signatureValue": "AA=="
+                }
+            }
+        "#;
+
+        let document = block_on(SignedDocument::<Frozen, Frozen>::parse_json_str(
+            DOCUMENT, None,
+        ))
+        .unwrap();
+        assert_eq_dataset!(
+            document.document.0,
+            parse_nq(r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" ."#)
+        );
+        let [signature] = document.signatures() else {
+            panic!("expected a single signature");
+        };
+        assert_eq!(
+            signature.created().unwrap().unwrap(),
+            "2024-01-01T00:00:00Z".into(),
+        );
+    }
+
+    #[test]
+    fn reuse_parser() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let document = |content: &str| {
+            load_doc(&format!(
+                r#"{{
+                    "@context": "https://w3id.org/security/v1",
+                    "https://www.w3.org/ns/activitystreams#content": "{}",
+                    "signature": {{
+                        "type": "RsaSignature2017",
+                        "creator": "https://example.com/#me",
+                        "signatureValue": "AA=="
+                    }}
+                }}"#,
+                content,
+            ))
+        };
+
+        let parser = <DocumentParser>::new();
+        assert_send_sync(&parser);
+
+        let (first, second) = block_on(futures_util::future::join(
+            parser.parse::<LightDataset, LightDataset>(document("first")),
+            parser.parse::<LightDataset, LightDataset>(document("second")),
+        ));
+        assert_eq_dataset!(
+            first.unwrap().document,
+            parse_nq(r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "first" ."#)
+        );
+        assert_eq_dataset!(
+            second.unwrap().document,
+            parse_nq(r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "second" ."#)
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn preloaded_then_http() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v1",
+                "https://www.w3.org/ns/activitystreams#content": "Hello, world!",
+                "signature": {
+                    "type": "RsaSignature2017",
+                    "creator": "https://example.com/#me",
+                    "signatureValue": "AA=="
+                }
+            }
+        "#;
+
+        // The well-known context is served without touching the network.
+        let document = block_on(
+            DocumentParser::preloaded_then_http()
+                .parse::<LightDataset, LightDataset>(load_doc(DOCUMENT)),
+        )
+        .unwrap();
+        assert_eq!(
+            document.preloaded_contexts(),
+            [Iri::new_unchecked("https://w3id.org/security/v1".into())],
+        );
+        assert_eq_dataset!(
+            document.document,
+            parse_nq(r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" ."#)
+        );
+    }
+
+    #[test]
+    fn canonicalization_algorithm() {
+        const DOCUMENT: &str = r#"
+            {
+                "@context": "https://w3id.org/security/v1",
+                "signature": {
+                    "type": "RsaSignature2017",
+                    "creator": "https://example.com/#me",
+                    "normalizationAlgorithm": "{}",
+                    "signatureValue": "AA=="
+                }
+            }
+        "#;
+
+        let parse = |algorithm: &str| {
+            block_on(SignedDocument::<LightDataset>::parse(load_doc(
+                &DOCUMENT.replace("{}", algorithm),
+            )))
+        };
+
+        parse("URDNA2015").unwrap();
+        match parse("https://example.com/#c14n") {
+            Err(Error::UnsupportedCanonicalization(iri)) => {
+                assert_eq!(&*iri, "https://example.com/#c14n")
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[cfg(all(feature = "serde", feature = "thread-rng"))]
+    #[test]
+    fn roundtrip_serialized_signature() {
+        use crate::serde::InsertSignature;
+        use crate::SignOptions;
+
+        #[derive(serde::Serialize)]
+        struct Note<'a> {
+            #[serde(rename = "@context")]
+            context: (&'a str, Context<'a>),
+            #[serde(rename = "type")]
+            kind: &'a str,
+            content: &'a str,
+        }
+
+        #[derive(serde::Serialize)]
+        struct Context<'a> {
+            content: &'a str,
+        }
+
+        const DATASET: &str = r#"
+            _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+        "#;
+
+        let note = Note {
+            context: (
+                "https://w3id.org/security/v1",
+                Context {
+                    content: "https://www.w3.org/ns/activitystreams#content",
+                },
+            ),
+            kind: "https://www.w3.org/ns/activitystreams#Note",
+            content: "Hello, world!",
+        };
+
+        let key = rsa_private_key();
+        let signature = <SignOptions<'_, '_>>::new()
+            .expires_in(core::time::Duration::from_secs(60 * 60))
+            .sign_rsa_signature_2017(
+                &parse_nq(DATASET),
+                &key,
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+
+        let json = json_syntax::to_value_with(InsertSignature::new(&note, &signature), || {
+            locspan::Location::new(
+                Iri::new_unchecked(Arc::from(PLACEHOLDER_IRI)),
+                Default::default(),
+            )
+        })
+        .unwrap();
+        let document = RemoteDocument::new(None, None, json);
+        let document = block_on(SignedDocument::<LightDataset>::parse(document)).unwrap();
+
+        assert_eq_dataset!(document.document, parse_nq(DATASET));
+        assert_eq!(
+            document.signatures()[0].expires().unwrap().unwrap(),
+            signature.expires.unwrap().into()
+        );
+        document.verify_rsa_signature_2017(key.as_ref()).unwrap();
     }
 }
@@ -0,0 +1,315 @@
+use core::future::Future;
+
+use futures_util::future::BoxFuture;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::RsaPublicKey;
+use sophia_api::dataset::Dataset;
+use sophia_api::quad::Quad;
+use sophia_api::term::{matcher, Term, TermKind};
+use sophia_iri::{Iri, IriRef};
+
+use crate::common::consts;
+
+/// A resolver of the public key of a signature from its `creator` IRI.
+///
+/// This is implemented for closures of the type `Fn(IriRef<&str>) -> Fut`, where `Fut` is a
+/// `'static` future resolving to `Result<RsaPublicKey, E>`, e.g., a
+/// `BoxFuture<'static, Result<RsaPublicKey, E>>`.
+pub trait KeyResolver {
+    /// The error raised when the key could not be resolved.
+    type Error;
+
+    /// Resolves the public key identified by the `creator` IRI.
+    fn resolve<'a>(
+        &'a self,
+        creator: IriRef<&'a str>,
+    ) -> BoxFuture<'a, Result<RsaPublicKey, Self::Error>>;
+}
+
+impl<F, Fut, E> KeyResolver for F
+where
+    F: Fn(IriRef<&str>) -> Fut,
+    Fut: Future<Output = Result<RsaPublicKey, E>> + Send + 'static,
+{
+    type Error = E;
+
+    fn resolve<'a>(&'a self, creator: IriRef<&'a str>) -> BoxFuture<'a, Result<RsaPublicKey, E>> {
+        Box::pin(self(creator))
+    }
+}
+
+/// Error while extracting a public key from a controller document.
+#[derive(Debug, thiserror::Error)]
+pub enum ExtractKeyError<E> {
+    /// The controller dataset raised an error.
+    #[error("Error from dataset: {0}")]
+    Dataset(E),
+    /// No key node matching the `creator` IRI was found.
+    #[error("No key node matching the creator")]
+    NoMatchingKey,
+    /// The `publicKeyPem` of the key node was not a valid RSA public key PEM.
+    #[error("Bad public key PEM: {0}")]
+    BadPem(rsa::pkcs8::spki::Error),
+    /// The key node didn't link to the expected owner.
+    #[error("The key is not owned by the expected owner")]
+    OwnerMismatch,
+}
+
+/// Extracts the RSA public key identified by the `creator` IRI from a `controller` document, e.g.,
+/// an ActivityPub actor.
+///
+/// The key is read from the `publicKeyPem` property of either:
+///
+/// - the node identified by the `creator` IRI,
+/// - a `publicKey` of the node identified by the `creator` IRI, or
+/// - a blank node `publicKey` of the node identified by the `creator` IRI without the fragment
+///   (e.g., `https://example.com/actor` for `https://example.com/actor#main-key`), provided that it
+///   is the only `publicKey` of that node.
+///
+/// Note that the last case accepts the key whatever the fragment of the `creator` is, since a blank
+/// node has no identifier to compare it with.
+///
+/// The PEM may be either in the SPKI (`BEGIN PUBLIC KEY`) or the PKCS#1 (`BEGIN RSA PUBLIC KEY`)
+/// format.
+pub fn extract_public_key<D>(
+    controller: &D,
+    creator: IriRef<&str>,
+) -> Result<RsaPublicKey, ExtractKeyError<D::Error>>
+where
+    D: Dataset,
+{
+    if let Some(pem) = public_key_pem(controller, creator)? {
+        return parse_pem(&pem);
+    }
+
+    for quad in controller.quads_matching(
+        [creator],
+        [Iri::new_unchecked(consts::PUBLIC_KEY)],
+        matcher::Any,
+        matcher::Any,
+    ) {
+        let key = quad.map_err(ExtractKeyError::Dataset)?.to_o();
+        if let Some(pem) = public_key_pem(controller, key)? {
+            return parse_pem(&pem);
+        }
+    }
+
+    // A blank node has no fragment to match against that of the `creator`, so it is only taken
+    // when it is the sole key of the owner and cannot be mistaken for another one.
+    if let Some((owner, _)) = creator.as_str().split_once('#') {
+        let mut keys = controller.quads_matching(
+            [IriRef::new_unchecked(owner)],
+            [Iri::new_unchecked(consts::PUBLIC_KEY)],
+            matcher::Any,
+            matcher::Any,
+        );
+        if let (Some(quad), None) = (keys.next(), keys.next()) {
+            let key = quad.map_err(ExtractKeyError::Dataset)?.to_o();
+            if key.is_blank_node() {
+                if let Some(pem) = public_key_pem(controller, key)? {
+                    return parse_pem(&pem);
+                }
+            }
+        }
+    }
+
+    Err(ExtractKeyError::NoMatchingKey)
+}
+
+/// Checks that the key identified by `key_id` belongs to the `expected_owner`, e.g., the actor that
+/// an object signed with the key is attributed to, according to a `controller` document.
+///
+/// The key node must link to the `expected_owner` with either the `owner` or the `controller`
+/// property of the Security Vocabulary. A valid signature alone doesn't tell that the key is
+/// authorized to speak for the actor, so this is commonly checked on top of the verification.
+pub fn check_key_controller<D>(
+    controller: &D,
+    key_id: IriRef<&str>,
+    expected_owner: IriRef<&str>,
+) -> Result<(), ExtractKeyError<D::Error>>
+where
+    D: Dataset,
+{
+    controller
+        .quads_matching(
+            [key_id],
+            [
+                Iri::new_unchecked(consts::OWNER),
+                Iri::new_unchecked(consts::CONTROLLER),
+            ],
+            [expected_owner],
+            matcher::Any,
+        )
+        .next()
+        .ok_or(ExtractKeyError::OwnerMismatch)?
+        .map(|_| ())
+        .map_err(ExtractKeyError::Dataset)
+}
+
+fn public_key_pem<D, T>(
+    controller: &D,
+    node: T,
+) -> Result<Option<Box<str>>, ExtractKeyError<D::Error>>
+where
+    D: Dataset,
+    T: Term,
+{
+    controller
+        .quads_matching(
+            [node],
+            [Iri::new_unchecked(consts::PUBLIC_KEY_PEM)],
+            TermKind::Literal,
+            matcher::Any,
+        )
+        .filter_map(|q| {
+            q.map(|q| q.to_o().lexical_form().map(Into::into))
+                .transpose()
+        })
+        .next()
+        .transpose()
+        .map_err(ExtractKeyError::Dataset)
+}
+
+fn parse_pem<E>(pem: &str) -> Result<RsaPublicKey, ExtractKeyError<E>> {
+    RsaPublicKey::from_public_key_pem(pem)
+        .or_else(|e| RsaPublicKey::from_pkcs1_pem(pem).map_err(|_| ExtractKeyError::BadPem(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rsa::pkcs1::EncodeRsaPublicKey;
+    use rsa::pkcs8::{EncodePublicKey, LineEnding};
+    use sophia_inmem::dataset::LightDataset;
+
+    use crate::util::test::{parse_nq, rsa_private_key};
+
+    use super::*;
+
+    fn controller(template: &str, pem: &str) -> LightDataset {
+        parse_nq(&template.replace("PEM", &pem.escape_default().to_string()))
+    }
+
+    #[test]
+    fn extract_public_key() {
+        let key = rsa_private_key().to_public_key();
+        let spki = key.to_public_key_pem(LineEnding::LF).unwrap();
+        let pkcs1 = key.to_pkcs1_pem(LineEnding::LF).unwrap();
+        let creator = IriRef::new_unchecked("https://example.com/actor#main-key");
+
+        // The key node itself.
+        let dataset = controller(
+            r#"<https://example.com/actor#main-key> <https://w3id.org/security#publicKeyPem> "PEM" ."#,
+            &spki,
+        );
+        assert_eq!(super::extract_public_key(&dataset, creator).unwrap(), key);
+
+        // The actor with an identified key node.
+        let dataset = controller(
+            r#"
+                <https://example.com/actor> <https://w3id.org/security#publicKey> <https://example.com/actor#main-key> .
+                <https://example.com/actor#main-key> <https://w3id.org/security#publicKeyPem> "PEM" .
+            "#,
+            &pkcs1,
+        );
+        assert_eq!(super::extract_public_key(&dataset, creator).unwrap(), key);
+
+        // The actor with a blank key node.
+        let dataset = controller(
+            r#"
+                <https://example.com/actor> <https://w3id.org/security#publicKey> _:b0 .
+                _:b0 <https://w3id.org/security#publicKeyPem> "PEM" .
+            "#,
+            &spki,
+        );
+        assert_eq!(super::extract_public_key(&dataset, creator).unwrap(), key);
+
+        // The fragment is not checked against a blank key node.
+        assert_eq!(
+            super::extract_public_key(
+                &dataset,
+                IriRef::new_unchecked("https://example.com/actor#other-key"),
+            )
+            .unwrap(),
+            key,
+        );
+
+        // Multiple key nodes, so that the blank one may not be the one meant by the creator.
+        let dataset = controller(
+            r#"
+                <https://example.com/actor> <https://w3id.org/security#publicKey> _:b0 .
+                <https://example.com/actor> <https://w3id.org/security#publicKey> <https://example.com/actor#other-key> .
+                _:b0 <https://w3id.org/security#publicKeyPem> "PEM" .
+                <https://example.com/actor#other-key> <https://w3id.org/security#publicKeyPem> "PEM" .
+            "#,
+            &spki,
+        );
+        assert!(matches!(
+            super::extract_public_key(&dataset, creator),
+            Err(ExtractKeyError::NoMatchingKey)
+        ));
+
+        // A key node of another identifier.
+        let dataset = controller(
+            r#"
+                <https://example.com/actor> <https://w3id.org/security#publicKey> <https://example.com/actor#other-key> .
+                <https://example.com/actor#other-key> <https://w3id.org/security#publicKeyPem> "PEM" .
+            "#,
+            &spki,
+        );
+        assert!(matches!(
+            super::extract_public_key(&dataset, creator),
+            Err(ExtractKeyError::NoMatchingKey)
+        ));
+
+        let dataset = controller(
+            r#"<https://example.com/actor#main-key> <https://w3id.org/security#publicKeyPem> "PEM" ."#,
+            "not a PEM",
+        );
+        assert!(matches!(
+            super::extract_public_key(&dataset, creator),
+            Err(ExtractKeyError::BadPem(_))
+        ));
+    }
+
+    #[test]
+    fn check_key_controller() {
+        let key_id = IriRef::new_unchecked("https://example.com/actor#main-key");
+        let actor = IriRef::new_unchecked("https://example.com/actor");
+
+        let dataset = parse_nq(
+            r#"<https://example.com/actor#main-key> <https://w3id.org/security#owner> <https://example.com/actor> ."#,
+        );
+        super::check_key_controller(&dataset, key_id, actor).unwrap();
+        assert!(matches!(
+            super::check_key_controller(
+                &dataset,
+                key_id,
+                IriRef::new_unchecked("https://example.com/other-actor"),
+            ),
+            Err(ExtractKeyError::OwnerMismatch)
+        ));
+        assert!(matches!(
+            super::check_key_controller(
+                &dataset,
+                IriRef::new_unchecked("https://example.com/actor#other-key"),
+                actor,
+            ),
+            Err(ExtractKeyError::OwnerMismatch)
+        ));
+
+        let dataset = parse_nq(
+            r#"<https://example.com/actor#main-key> <https://w3id.org/security#controller> <https://example.com/actor> ."#,
+        );
+        super::check_key_controller(&dataset, key_id, actor).unwrap();
+
+        // Only the links from the key node count.
+        let dataset = parse_nq(
+            r#"<https://example.com/actor> <https://w3id.org/security#publicKey> <https://example.com/actor#main-key> ."#,
+        );
+        assert!(matches!(
+            super::check_key_controller(&dataset, key_id, actor),
+            Err(ExtractKeyError::OwnerMismatch)
+        ));
+    }
+}
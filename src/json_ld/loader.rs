@@ -1,3 +1,9 @@
+pub mod caching;
+#[cfg(feature = "http")]
+pub mod http;
 pub mod preloaded;
 
+pub use self::caching::CachingLoader;
+#[cfg(feature = "http")]
+pub use self::http::{HttpLoader, LoaderConfig};
 pub use self::preloaded::PreloadedLoader;
@@ -0,0 +1,182 @@
+use core::future;
+use core::hash::Hash;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use json_ld::future::BoxFuture;
+use json_ld::{Loader, RemoteDocument};
+use rdf_types::IriVocabularyMut;
+use sophia_jsonld::vocabulary::ArcIri;
+
+/// A JSON-LD document [`Loader`] wrapper that memoizes the documents successfully loaded by the
+/// inner loader, keyed by their IRIs.
+///
+/// The clones of a `CachingLoader` share the cache, so a parser yielding the clones of a loader,
+/// e.g., one built with `JsonLdOptions::with_document_loader`, fetches each remote context at most
+/// once in its lifetime:
+///
+/// ```no_run
+/// # #[cfg(feature = "http")] {
+/// use rsa_signature_2017::json_ld::loader::{CachingLoader, HttpLoader, PreloadedLoader};
+/// use rsa_signature_2017::json_ld::{DocumentParser, JsonLdOptions};
+/// use sophia_jsonld::loader::ChainLoader;
+///
+/// let loader = ChainLoader::new(PreloadedLoader::new(), CachingLoader::new(HttpLoader::new()));
+/// let parser = DocumentParser::with_options(JsonLdOptions::new().with_document_loader(loader));
+/// # }
+/// ```
+///
+/// On the other hand, each loader created with `Default`, e.g., by `DefaultLoaderFactory`, has its
+/// own cache. Errors are not cached, and the cache is never evicted, so the loader shouldn't be
+/// shared with untrusted documents referring to arbitrarily many contexts.
+pub struct CachingLoader<L, I = ArcIri> {
+    inner: L,
+    cache: Arc<Mutex<HashMap<I, RemoteDocument<I>>>>,
+}
+
+impl<L, I> CachingLoader<L, I> {
+    pub fn new(inner: L) -> Self {
+        Self {
+            inner,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Returns the number of the cached documents.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no documents are cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes the cached documents from all the clones of this loader.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<I, RemoteDocument<I>>> {
+        // The map is never left in an inconsistent state.
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<L: Clone, I> Clone for CachingLoader<L, I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<L: Default, I> Default for CachingLoader<L, I> {
+    fn default() -> Self {
+        Self::new(L::default())
+    }
+}
+
+impl<L, I> Loader<I, locspan::Location<I>> for CachingLoader<L, I>
+where
+    L: Loader<I, locspan::Location<I>, Output = json_syntax::Value<locspan::Location<I>>>,
+    L::Error: Send,
+    I: Clone + Eq + Hash + Send + Sync,
+{
+    type Output = json_syntax::Value<locspan::Location<I>>;
+    type Error = L::Error;
+
+    fn load_with<'a>(
+        &'a mut self,
+        vocabulary: &'a mut (impl Sync + Send + IriVocabularyMut<Iri = I>),
+        url: I,
+    ) -> BoxFuture<'a, Result<RemoteDocument<I>, Self::Error>>
+    where
+        I: 'a,
+    {
+        if let Some(document) = self.lock().get(&url) {
+            trace_event!(
+                debug,
+                iri = vocabulary.iri(&url).as_ref().map(|iri| iri.as_str()),
+                "serving a cached document",
+            );
+            return Box::pin(future::ready(Ok(document.clone())));
+        }
+
+        let cache = &self.cache;
+        let load = self.inner.load_with(vocabulary, url.clone());
+        Box::pin(async move {
+            let document = load.await?;
+            cache
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(url, document.clone());
+            Ok(document)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures_executor::block_on;
+    use sophia_iri::Iri;
+    use sophia_jsonld::vocabulary::ArcVoc;
+
+    use super::*;
+
+    /// A loader that serves an empty object for any IRI and counts the loads.
+    #[derive(Clone, Default)]
+    struct CountingLoader {
+        loads: Arc<AtomicUsize>,
+    }
+
+    impl Loader<ArcIri, locspan::Location<ArcIri>> for CountingLoader {
+        type Output = json_syntax::Value<locspan::Location<ArcIri>>;
+        type Error = ();
+
+        fn load_with<'a>(
+            &'a mut self,
+            _: &'a mut (impl Sync + Send + IriVocabularyMut<Iri = ArcIri>),
+            url: ArcIri,
+        ) -> BoxFuture<'a, Result<RemoteDocument<ArcIri>, Self::Error>>
+        where
+            ArcIri: 'a,
+        {
+            self.loads.fetch_add(1, Ordering::Relaxed);
+            let document = locspan::Meta::new(
+                json_syntax::Value::Object(Default::default()),
+                locspan::Location::new(url.clone(), Default::default()),
+            );
+            Box::pin(future::ready(Ok(RemoteDocument::new(
+                Some(url),
+                None,
+                document,
+            ))))
+        }
+    }
+
+    #[test]
+    fn shares_cache_among_clones() {
+        let counter = CountingLoader::default();
+        let mut loader = CachingLoader::new(counter.clone());
+        let mut clone = loader.clone();
+        let a = Iri::new_unchecked(Arc::from("https://example.com/a"));
+        let b = Iri::new_unchecked(Arc::from("https://example.com/b"));
+
+        block_on(loader.load_with(&mut ArcVoc {}, a.clone())).unwrap();
+        block_on(clone.load_with(&mut ArcVoc {}, a.clone())).unwrap();
+        assert_eq!(counter.loads.load(Ordering::Relaxed), 1);
+
+        block_on(clone.load_with(&mut ArcVoc {}, b)).unwrap();
+        assert_eq!(counter.loads.load(Ordering::Relaxed), 2);
+        assert_eq!(loader.len(), 2);
+
+        loader.clear();
+        assert!(clone.is_empty());
+        block_on(clone.load_with(&mut ArcVoc {}, a)).unwrap();
+        assert_eq!(counter.loads.load(Ordering::Relaxed), 3);
+    }
+}
@@ -0,0 +1,315 @@
+use core::marker::PhantomData;
+use core::time::Duration;
+
+use json_ld::future::BoxFuture;
+use json_ld::{Loader, RemoteDocument};
+use json_syntax::Parse as _;
+use rdf_types::IriVocabularyMut;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::StatusCode;
+use sophia_jsonld::vocabulary::ArcIri;
+
+/// The `Accept` header that [`HttpLoader`] sends.
+pub const ACCEPT_HEADER: &str =
+    "application/activity+json, application/ld+json, application/json;q=0.9";
+
+/// Limits on the requests that [`HttpLoader`] sends.
+///
+/// The documents to be verified may refer to arbitrary remote contexts, so an untrusted document
+/// could otherwise make the verifier wait for a slow server or buffer a huge response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LoaderConfig {
+    /// The maximum duration of each request, from connecting until the whole response body has
+    /// been read. Defaults to 10 seconds.
+    pub timeout: Duration,
+    /// The maximum size of each response body in bytes. Defaults to 1 MiB.
+    pub max_bytes: usize,
+}
+
+/// The media types that [`HttpLoader`] treats as JSON-LD.
+const JSON_LD_MEDIA_TYPES: [&str; 3] = [
+    "application/activity+json",
+    "application/ld+json",
+    "application/json",
+];
+
+/// A JSON-LD document [`Loader`] implementation that fetches the documents over HTTP(S).
+///
+/// Unlike `ReqwestLoader` of the `json-ld` crate, this loader also requests and accepts the
+/// `application/activity+json` media type, which ActivityPub servers commonly serve the actors and
+/// the keys as, sometimes exclusively.
+///
+/// The loader follows redirects, but the loaded document keeps the requested IRI as its URL. The
+/// loader uses [`reqwest`], so it has to be run within a Tokio runtime.
+pub struct HttpLoader<I = ArcIri> {
+    client: reqwest::Client,
+    config: LoaderConfig,
+    marker: PhantomData<fn() -> I>,
+}
+
+/// Error while fetching a document with [`HttpLoader`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum HttpLoaderError {
+    /// The vocabulary didn't know the IRI to fetch.
+    #[error("Unknown IRI")]
+    UnknownIri,
+    /// The HTTP request failed.
+    #[error("HTTP request failed: {0}")]
+    Request(#[source] reqwest::Error),
+    /// The server responded with a status other than a success.
+    #[error("Unexpected HTTP status: {0}")]
+    Status(StatusCode),
+    /// The request didn't complete within the [`LoaderConfig::timeout`].
+    #[error("HTTP request timed out")]
+    Timeout,
+    /// The response body exceeded the [`LoaderConfig::max_bytes`].
+    #[error("Response body too large (limit: {limit} bytes)")]
+    TooLarge {
+        /// The maximum size of the response body.
+        limit: usize,
+    },
+    /// The server responded with a media type other than a JSON(-LD) one.
+    #[error("Unsupported content type: {0:?}")]
+    ContentType(Option<Box<str>>),
+    /// The response body was not a valid JSON document.
+    #[error("Invalid JSON document: {0}")]
+    Json(Box<str>),
+}
+
+impl<I> HttpLoader<I> {
+    pub fn new() -> Self {
+        Self::with_client(reqwest::Client::new())
+    }
+
+    /// Creates a loader that sends the requests with the `client`, e.g., one configured with a
+    /// timeout or a `User-Agent`.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            config: LoaderConfig::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the limits on the requests, which take precedence over the timeout of the client.
+    pub fn with_config(self, config: LoaderConfig) -> Self {
+        Self { config, ..self }
+    }
+
+    pub fn config(&self) -> &LoaderConfig {
+        &self.config
+    }
+}
+
+impl LoaderConfig {
+    pub fn new(timeout: Duration, max_bytes: usize) -> Self {
+        Self { timeout, max_bytes }
+    }
+}
+
+impl Default for LoaderConfig {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10), 1024 * 1024)
+    }
+}
+
+impl<I> Clone for HttpLoader<I> {
+    fn clone(&self) -> Self {
+        Self::with_client(self.client.clone()).with_config(self.config)
+    }
+}
+
+impl<I> Default for HttpLoader<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> Loader<I, locspan::Location<I>> for HttpLoader<I>
+where
+    I: Clone + Send + Sync,
+{
+    type Output = json_syntax::Value<locspan::Location<I>>;
+    type Error = HttpLoaderError;
+
+    fn load_with<'a>(
+        &'a mut self,
+        vocabulary: &mut impl IriVocabularyMut<Iri = I>,
+        url: I,
+    ) -> BoxFuture<'a, Result<RemoteDocument<I>, Self::Error>>
+    where
+        I: 'a,
+    {
+        let iri = vocabulary.iri(&url).map(|iri| iri.as_str().to_owned());
+        let config = self.config;
+        Box::pin(async move {
+            let iri = iri.ok_or(HttpLoaderError::UnknownIri)?;
+            trace_event!(debug, iri = iri.as_str(), "fetching a remote document");
+
+            let mut response = self
+                .client
+                .get(&iri)
+                .header(ACCEPT, ACCEPT_HEADER)
+                .timeout(config.timeout)
+                .send()
+                .await
+                .map_err(HttpLoaderError::from_reqwest)?;
+            if !response.status().is_success() {
+                return Err(HttpLoaderError::Status(response.status()));
+            }
+
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok());
+            let media_type = match content_type.and_then(json_ld_media_type) {
+                Some(media_type) => media_type,
+                None => return Err(HttpLoaderError::ContentType(content_type.map(Into::into))),
+            };
+
+            let too_large = HttpLoaderError::TooLarge {
+                limit: config.max_bytes,
+            };
+            if response
+                .content_length()
+                .is_some_and(|len| len > config.max_bytes as u64)
+            {
+                return Err(too_large);
+            }
+            let mut body = Vec::new();
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(HttpLoaderError::from_reqwest)?
+            {
+                if body.len() + chunk.len() > config.max_bytes {
+                    return Err(too_large);
+                }
+                body.extend_from_slice(&chunk);
+            }
+            let body =
+                String::from_utf8(body).map_err(|e| HttpLoaderError::Json(e.to_string().into()))?;
+
+            let document = json_syntax::Value::parse_str(&body, |span| {
+                locspan::Location::new(url.clone(), span)
+            })
+            .map_err(|e| HttpLoaderError::Json(e.to_string().into()))?;
+
+            Ok(RemoteDocument::new(
+                Some(url),
+                Some(media_type.parse().unwrap()),
+                document,
+            ))
+        })
+    }
+}
+
+impl HttpLoaderError {
+    fn from_reqwest(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            HttpLoaderError::Timeout
+        } else {
+            HttpLoaderError::Request(e)
+        }
+    }
+}
+
+/// Returns the JSON-LD media type of the `Content-Type` header value, if any.
+fn json_ld_media_type(content_type: &str) -> Option<&'static str> {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    JSON_LD_MEDIA_TYPES
+        .into_iter()
+        .find(|candidate| media_type.eq_ignore_ascii_case(candidate))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread;
+
+    use sophia_iri::Iri;
+    use sophia_jsonld::vocabulary::ArcVoc;
+
+    use super::*;
+
+    /// Serves a JSON-LD response with the `body` to a single request after the `delay`, and returns
+    /// the URL to request.
+    fn serve_once(body: String, content_length: bool, delay: Duration) -> ArcIri {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]);
+            thread::sleep(delay);
+            let mut response =
+                "HTTP/1.1 200 OK\r\nContent-Type: application/ld+json\r\nConnection: close\r\n"
+                    .to_owned();
+            if content_length {
+                response += &format!("Content-Length: {}\r\n", body.len());
+            }
+            response += "\r\n";
+            response += &body;
+            let _ = stream.write_all(response.as_bytes());
+        });
+        Iri::new_unchecked(Arc::from(format!("http://{}/", addr)))
+    }
+
+    fn load(config: LoaderConfig, url: ArcIri) -> Result<(), HttpLoaderError> {
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let mut loader = HttpLoader::with_client(client).with_config(config);
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(loader.load_with(&mut ArcVoc {}, url))
+            .map(drop)
+    }
+
+    #[test]
+    fn limits() {
+        let config = LoaderConfig::new(Duration::from_millis(500), 64);
+        let body = format!(r#"{{"@context":{{"a":"{}"}}}}"#, "a".repeat(32));
+
+        let url = serve_once(body.clone(), true, Duration::ZERO);
+        load(config, url).unwrap();
+
+        let large = body.replace("aaaa", "aaaaaaaa");
+        for content_length in [true, false] {
+            let url = serve_once(large.clone(), content_length, Duration::ZERO);
+            let e = load(config, url).unwrap_err();
+            assert!(
+                matches!(e, HttpLoaderError::TooLarge { limit: 64 }),
+                "{:?}",
+                e
+            );
+        }
+
+        let url = serve_once(body, true, Duration::from_secs(2));
+        let e = load(config, url).unwrap_err();
+        assert!(matches!(e, HttpLoaderError::Timeout), "{:?}", e);
+    }
+
+    #[test]
+    fn media_type() {
+        assert_eq!(
+            json_ld_media_type("application/activity+json"),
+            Some("application/activity+json"),
+        );
+        assert_eq!(
+            json_ld_media_type(
+                r#"application/ld+json; profile="https://www.w3.org/ns/activitystreams""#
+            ),
+            Some("application/ld+json"),
+        );
+        assert_eq!(
+            json_ld_media_type("Application/JSON; charset=utf-8"),
+            Some("application/json"),
+        );
+        assert_eq!(json_ld_media_type("text/html"), None);
+        assert_eq!(json_ld_media_type("application/jsonx"), None);
+    }
+}
@@ -1,8 +1,12 @@
 mod context;
 
+use core::cell::RefCell;
 use core::fmt::{self, Display, Formatter};
-use core::future;
+use core::future::{self, Future};
 use core::marker::PhantomData;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
 
 use json_ld::future::BoxFuture;
 use json_ld::{Loader, RemoteDocument};
@@ -35,6 +39,34 @@ pub struct NotPreloaded<I> {
     iri: I,
 }
 
+/// A future returned by [`record_hits`].
+pub(crate) struct RecordHits<F: Future> {
+    future: Pin<Box<F>>,
+    hits: Vec<Box<str>>,
+}
+
+/// Restores the previous recorder of the thread when dropped, even if the future panicked.
+struct RecorderGuard<'a> {
+    hits: &'a mut Vec<Box<str>>,
+    prev: Option<Vec<Box<str>>>,
+}
+
+thread_local! {
+    /// The IRIs served by `PreloadedLoader` during the current poll of a `RecordHits`, if any.
+    static HITS: RefCell<Option<Vec<Box<str>>>> = const { RefCell::new(None) };
+}
+
+/// Runs the `future`, recording the IRIs that any `PreloadedLoader` serves while polling it.
+///
+/// The recorder is installed only for the duration of each poll, so the future can be moved across
+/// threads between polls.
+pub(crate) fn record_hits<F: Future>(future: F) -> RecordHits<F> {
+    RecordHits {
+        future: Box::pin(future),
+        hits: Vec::new(),
+    }
+}
+
 impl<I> PreloadedLoader<I>
 where
     I: Clone + Send,
@@ -44,6 +76,14 @@ where
     }
 }
 
+impl<I> Clone for PreloadedLoader<I> {
+    fn clone(&self) -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<I> Default for PreloadedLoader<I>
 where
     I: Clone + Send,
@@ -66,23 +106,64 @@ where
         &'a mut self,
         vocabulary: &mut impl IriVocabularyMut<Iri = I>,
         url: I,
-    ) -> BoxFuture<'_, Result<RemoteDocument<I>, Self::Error>>
+    ) -> BoxFuture<'a, Result<RemoteDocument<I>, Self::Error>>
     where
         I: 'a,
     {
         let ret = if let Some(document) = context::preloaded(vocabulary, &url) {
+            HITS.with(|hits| {
+                if let (Some(hits), Some(iri)) = (&mut *hits.borrow_mut(), vocabulary.iri(&url)) {
+                    if !hits.iter().any(|hit| **hit == *iri.as_str()) {
+                        hits.push(iri.as_str().into());
+                    }
+                }
+            });
+            trace_event!(
+                debug,
+                iri = vocabulary.iri(&url).as_ref().map(|iri| iri.as_str()),
+                "serving a preloaded context",
+            );
             Ok(RemoteDocument::new(
                 Some(url),
                 Some("application/ld+json".parse().unwrap()),
                 document,
             ))
         } else {
+            trace_event!(
+                debug,
+                iri = vocabulary.iri(&url).as_ref().map(|iri| iri.as_str()),
+                "the context is not preloaded",
+            );
             Err(NotPreloaded { iri: url })
         };
         Box::pin(future::ready(ret))
     }
 }
 
+impl<F: Future> Future for RecordHits<F> {
+    type Output = (F::Output, Vec<Box<str>>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let prev = HITS.with(|hits| hits.replace(Some(mem::take(&mut this.hits))));
+        let guard = RecorderGuard {
+            hits: &mut this.hits,
+            prev,
+        };
+        let ret = this.future.as_mut().poll(cx);
+        drop(guard);
+        ret.map(|output| (output, mem::take(&mut this.hits)))
+    }
+}
+
+impl Drop for RecorderGuard<'_> {
+    fn drop(&mut self) {
+        *self.hits = HITS
+            .with(|hits| hits.replace(self.prev.take()))
+            .unwrap_or_default();
+    }
+}
+
 impl<I: Display> Display for NotPreloaded<I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "the IRI is not preloaded: {}", self.iri)
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use rand_core::{CryptoRng, RngCore};
+use rsa::RsaPrivateKey;
+use sophia_api::dataset::{CollectibleDataset, Dataset};
+use sophia_api::source::StreamError;
+use sophia_inmem::dataset::LightDataset;
+use sophia_iri::Iri;
+use sophia_jsonld::parser::JsonLdParser;
+use sophia_jsonld::JsonLdError;
+
+use crate::error::DatasetError;
+use crate::serde::InsertSignature;
+use crate::{sign, SignOptions};
+
+use super::{try_json_ld_qs, JsonLdOptions, PLACEHOLDER_IRI};
+
+/// Error while signing a JSON document with [`sign_json`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SignJsonError {
+    /// The document wasn't a JSON object.
+    #[error("Expected a JSON object")]
+    NotAnObject,
+    /// The document already had a `signature` entry and
+    /// [`SignOptions::reject_existing_signature`] was set.
+    #[error("The document already has a signature")]
+    ExistingSignature,
+    /// The document couldn't be expanded into RDF.
+    #[error("Unable to expand the document: {0}")]
+    Document(JsonLdError),
+    /// The expanded document couldn't be signed.
+    #[error(transparent)]
+    Sign(sign::Error<<LightDataset as Dataset>::Error>),
+}
+
+/// Signs a JSON-LD document given as a `serde_json::Value` with the `RsaSignature2017` suite and
+/// returns the document with the `signature` entry inserted.
+///
+/// An existing `signature` entry of the document is not signed and is replaced with the new one,
+/// unless [`SignOptions::reject_existing_signature`] is set.
+/// Only the contexts preloaded by [`PreloadedLoader`](super::loader::PreloadedLoader) are available
+/// to the JSON-LD processing, so the document fails to expand if it refers to other remote
+/// contexts.
+pub async fn sign_json<'sig, R>(
+    document: &serde_json::Value,
+    key: &RsaPrivateKey,
+    creator: Iri<&'sig str>,
+    options: &mut SignOptions<'sig, '_, R>,
+) -> Result<serde_json::Value, SignJsonError>
+where
+    R: RngCore + CryptoRng,
+{
+    let mut document = document.clone();
+    let object = document.as_object_mut().ok_or(SignJsonError::NotAnObject)?;
+    if object.remove("signature").is_some() && options.reject_existing_signature {
+        return Err(SignJsonError::ExistingSignature);
+    }
+
+    let location_iri = Iri::new_unchecked(Arc::from(PLACEHOLDER_IRI));
+    let json = json_syntax::to_value_with(&document, || {
+        locspan::Location::new(location_iri.clone(), locspan::Span::default())
+    })
+    // `serde_json::Value` always serializes successfully.
+    .unwrap();
+    let quads = JsonLdParser::new_with_options(<JsonLdOptions>::default())
+        .parse_json(&json_ld::RemoteDocument::new(None, None, json))
+        .await;
+    let quads = try_json_ld_qs(quads).map_err(SignJsonError::Document)?;
+    let dataset = LightDataset::from_quad_source(quads).map_err(|e| match e {
+        StreamError::SourceError(e) => match e {},
+        StreamError::SinkError(e) => {
+            SignJsonError::Sign(sign::Error::Dataset(DatasetError::Dataset(e)))
+        }
+    })?;
+
+    let signature = options
+        .sign_rsa_signature_2017(&dataset, key, creator)
+        .map_err(SignJsonError::Sign)?;
+
+    // The document is an object, into which the signature can always be inserted.
+    Ok(serde_json::to_value(InsertSignature::new(&document, &signature)).unwrap())
+}
+
+#[cfg(all(test, feature = "thread-rng"))]
+mod tests {
+    use futures_executor::block_on;
+    use sophia_inmem::dataset::LightDataset;
+
+    use crate::json_ld::SignedDocument;
+    use crate::util::test::rsa_private_key;
+
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let document = serde_json::json!({
+            "@context": [
+                "https://w3id.org/security/v1",
+                {
+                    "content": "https://www.w3.org/ns/activitystreams#content"
+                }
+            ],
+            "type": "https://www.w3.org/ns/activitystreams#Note",
+            "content": "Hello, world!",
+            "signature": "stale"
+        });
+
+        let key = rsa_private_key();
+        let signed = block_on(sign_json(
+            &document,
+            &key,
+            Iri::new_unchecked("https://example.com/#me"),
+            &mut <SignOptions<'_, '_>>::new(),
+        ))
+        .unwrap();
+        assert_eq!(signed["content"], "Hello, world!");
+        assert_eq!(signed["signature"]["type"], "RsaSignature2017");
+
+        let signed = block_on(SignedDocument::<LightDataset>::parse_json_str(
+            &signed.to_string(),
+            None,
+        ))
+        .unwrap();
+        signed
+            .verify_rsa_signature_2017(&key.to_public_key())
+            .unwrap();
+
+        let e = block_on(sign_json(
+            &serde_json::json!([]),
+            &key,
+            Iri::new_unchecked("https://example.com/#me"),
+            &mut <SignOptions<'_, '_>>::new(),
+        ))
+        .unwrap_err();
+        assert!(matches!(e, SignJsonError::NotAnObject));
+
+        let e = block_on(sign_json(
+            &document,
+            &key,
+            Iri::new_unchecked("https://example.com/#me"),
+            <SignOptions<'_, '_>>::new().reject_existing_signature(true),
+        ))
+        .unwrap_err();
+        assert!(matches!(e, SignJsonError::ExistingSignature));
+    }
+}
@@ -0,0 +1,181 @@
+//! Conversions between RSA keys and the JSON Web Key (JWK) format ([RFC 7517]).
+//!
+//! [RFC 7517]: <https://www.rfc-editor.org/rfc/rfc7517>
+
+use core::fmt::{self, Debug, Formatter};
+
+use base64::Engine as _;
+use rsa::traits::PublicKeyParts;
+use rsa::{BigUint, RsaPrivateKey, RsaPublicKey};
+
+/// An RSA key in the JWK format.
+///
+/// The key parameters are stored as base64url-encoded strings without padding, as they appear in
+/// the JSON representation.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct Jwk {
+    /// The key type, which must be `"RSA"`.
+    pub kty: String,
+    /// The modulus.
+    pub n: String,
+    /// The public exponent.
+    pub e: String,
+    /// The private exponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+    /// The first prime factor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<String>,
+    /// The second prime factor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    /// The first factor CRT exponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dp: Option<String>,
+    /// The second factor CRT exponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dq: Option<String>,
+    /// The first CRT coefficient.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qi: Option<String>,
+}
+
+/// Error while converting a JWK to an RSA key.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The `kty` parameter was not `"RSA"`.
+    #[error("Unsupported key type: {0}")]
+    UnsupportedKeyType(String),
+    /// A parameter required for the key was missing.
+    #[error("Missing parameter: {0}")]
+    MissingParameter(&'static str),
+    /// A parameter was not a valid base64url-encoded string.
+    #[error("Invalid base64url encoding of parameter: {0}")]
+    InvalidEncoding(&'static str),
+    /// The parameters didn't form a valid RSA key.
+    #[error("Invalid key: {0}")]
+    InvalidKey(rsa::Error),
+}
+
+impl Jwk {
+    /// Constructs a JWK representing the public key `key`.
+    pub fn from_rsa_public_key(key: &RsaPublicKey) -> Self {
+        Jwk {
+            kty: "RSA".into(),
+            n: encode(key.n()),
+            e: encode(key.e()),
+            d: None,
+            p: None,
+            q: None,
+            dp: None,
+            dq: None,
+            qi: None,
+        }
+    }
+
+    /// Converts the JWK to an RSA public key, ignoring the private key parameters if any.
+    pub fn to_rsa_public_key(&self) -> Result<RsaPublicKey, Error> {
+        self.check_kty()?;
+        RsaPublicKey::new(decode("n", &self.n)?, decode("e", &self.e)?).map_err(Error::InvalidKey)
+    }
+
+    /// Converts the JWK to an RSA private key.
+    ///
+    /// The prime factors are recovered from the private exponent if the `p` and `q` parameters are
+    /// absent. The CRT parameters (`dp`, `dq` and `qi`) are ignored and recomputed from the other
+    /// parameters.
+    pub fn to_rsa_private_key(&self) -> Result<RsaPrivateKey, Error> {
+        self.check_kty()?;
+        let d = self.d.as_deref().ok_or(Error::MissingParameter("d"))?;
+        let primes = match (self.p.as_deref(), self.q.as_deref()) {
+            (Some(p), Some(q)) => vec![decode("p", p)?, decode("q", q)?],
+            (None, None) => Vec::new(),
+            (None, Some(_)) => return Err(Error::MissingParameter("p")),
+            (Some(_), None) => return Err(Error::MissingParameter("q")),
+        };
+        RsaPrivateKey::from_components(
+            decode("n", &self.n)?,
+            decode("e", &self.e)?,
+            decode("d", d)?,
+            primes,
+        )
+        .map_err(Error::InvalidKey)
+    }
+
+    fn check_kty(&self) -> Result<(), Error> {
+        if self.kty == "RSA" {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedKeyType(self.kty.clone()))
+        }
+    }
+}
+
+impl From<&RsaPublicKey> for Jwk {
+    fn from(key: &RsaPublicKey) -> Self {
+        Jwk::from_rsa_public_key(key)
+    }
+}
+
+impl Debug for Jwk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Omitting the private key parameters.
+        f.debug_struct("Jwk")
+            .field("kty", &self.kty)
+            .field("n", &self.n)
+            .field("e", &self.e)
+            .finish_non_exhaustive()
+    }
+}
+
+fn encode(n: &BigUint) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(n.to_bytes_be())
+}
+
+fn decode(name: &'static str, value: &str) -> Result<BigUint, Error> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(value)
+        .map(|bytes| BigUint::from_bytes_be(&bytes))
+        .map_err(|_| Error::InvalidEncoding(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use rsa::traits::PrivateKeyParts;
+
+    use crate::util::test::rsa_private_key;
+
+    use super::*;
+
+    #[test]
+    fn public_key_roundtrip() {
+        let key = rsa_private_key().to_public_key();
+        let jwk = Jwk::from(&key);
+        assert_eq!(jwk.kty, "RSA");
+        assert_eq!(jwk.e, "AQAB");
+        assert_eq!(jwk.to_rsa_public_key().unwrap(), key);
+    }
+
+    #[test]
+    fn private_key() {
+        let key = rsa_private_key();
+        let mut jwk = Jwk {
+            d: Some(encode(key.d())),
+            p: Some(encode(&key.primes()[0])),
+            q: Some(encode(&key.primes()[1])),
+            ..Jwk::from(key.as_ref())
+        };
+        assert_eq!(jwk.to_rsa_private_key().unwrap(), key);
+
+        jwk.p = None;
+        jwk.q = None;
+        assert_eq!(jwk.to_rsa_private_key().unwrap().d(), key.d());
+
+        jwk.d = None;
+        assert!(matches!(
+            jwk.to_rsa_private_key(),
+            Err(Error::MissingParameter("d"))
+        ));
+    }
+}
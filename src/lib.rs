@@ -7,13 +7,45 @@
 //! GCA2015 algorithm (aka. URDNA2015), which the original `RsaSignature2017` algorithm uses.
 //!
 //! The difference between these algorithms is handling of some control characters. So, please just
-//! don't give it those characters, thanks!
+//! don't give it those characters, thanks! [`SignOptions::warn_control_chars`] can tell you if you
+//! did.
 //!
+//! ## WebAssembly
+//!
+//! `SystemTime::now` and `rand::thread_rng` are unavailable on the `wasm32-unknown-unknown`
+//! target, so the library can't fill in the datetime and the nonce of a signature or blind the RSA
+//! operation by itself there. Disable the `thread-rng` feature, enable the `wasm` feature, which
+//! makes the library fail with [`sign::Error::NoRng`] or [`sign::Error::NoClock`] instead of
+//! reaching those functions, and supply the datetime and an RNG explicitly, e.g., the current time
+//! from `Date.now()` of JavaScript and an RNG backed by `crypto.getRandomValues()`:
+//!
+//! ```ignore
+//! let signature = SignOptions::new()
+//!     .created_datetime(now_from_js())
+//!     .rng(&mut rng)
+//!     .sign_rsa_signature_2017(&dataset, &key, creator)?;
+//! ```
+//!
+//! Verification doesn't need an RNG. It only needs the current time to check
+//! [`VerifyOptions::max_age`], so set [`VerifyOptions::now`] along with it there, or the
+//! verification fails with [`verify::Error::NoClock`].
+//!
+//! ## Verify-only builds
+//!
+//! The signing and verification APIs are gated behind the `sign` and `verify` features
+//! respectively, both of which are enabled by default. A server that only verifies signatures can
+//! disable the default features and enable `verify` (along with `json-ld` etc.) to compile out the
+//! signing code and its RNG dependencies.
 //!
 //! [Linked Data Signatures]: <https://github.com/w3c-ccg/ld-signatures/blob/d0af56856684924156a94838f9482a27766bb2be/index.html>
 
 #![warn(rust_2018_idioms)]
 #![forbid(unsafe_op_in_unsafe_fn)]
+// The documentation links to the items that the `sign` or `verify` feature may compile out.
+#![cfg_attr(
+    not(all(feature = "sign", feature = "verify")),
+    allow(rustdoc::broken_intra_doc_links)
+)]
 
 #[cfg(not(feature = "std"))]
 compile_error!(concat!(
@@ -27,15 +59,39 @@ mod util;
 pub mod error;
 #[cfg(feature = "json-ld")]
 pub mod json_ld;
+#[cfg(feature = "jwk")]
+pub mod jwk;
 #[cfg(feature = "serde")]
 pub mod serde;
 pub mod sign;
+pub mod suite;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "verify")]
 pub mod verify;
 
 mod common;
+mod fingerprint;
 
-pub use self::sign::{sign_rsa_signature_2017, SignOptions, Signature};
-pub use self::verify::verify_rsa_signature_2017;
+pub use self::common::{
+    canonicalize_document, canonicalize_options, create_verify_hash_input, hash_dataset_hex,
+    GraphScope, SignatureOptions,
+};
+pub use self::fingerprint::{rsa_public_key_fingerprint, FingerprintEncoding};
+#[cfg(feature = "sign")]
+pub use self::sign::SignOptions;
+#[cfg(feature = "thread-rng")]
+pub use self::sign::{sign_all, sign_rsa_signature_2017};
+#[cfg(feature = "serde")]
+pub use self::sign::{ContextChoice, SignatureEncoding};
+pub use self::sign::{CreatorKey, Signature};
+pub use self::suite::SignatureSuite;
+pub use self::util::DigestWrite;
+#[cfg(feature = "verify")]
+pub use self::verify::{
+    verify_rsa_signature_2017, verify_rsa_signature_2017_any, verify_rsa_signature_2017_report,
+    AsRsaPublicKey, VerificationReport, Verifier, VerifyOptions,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize))]
@@ -44,37 +100,339 @@ pub enum SignatureType {
     RsaSignature2017,
 }
 
-#[cfg(test)]
+impl core::str::FromStr for SignatureType {
+    type Err = error::ParseSignatureTypeError;
+
+    /// Parses a `SignatureType` from a string, which may be either the bare term (e.g.
+    /// `"RsaSignature2017"`), the compact IRI with the `sec:` prefix or the full IRI.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s
+            .strip_prefix("https://w3id.org/security#")
+            .or_else(|| s.strip_prefix("sec:"))
+            .unwrap_or(s);
+        match name {
+            "RsaSignature2017" => Ok(SignatureType::RsaSignature2017),
+            _ => Err(error::ParseSignatureTypeError),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "thread-rng", feature = "verify"))]
 mod tests {
-    use rsa::{BigUint, RsaPrivateKey};
-    use sophia_iri::Iri;
+    use std::time::{Duration, SystemTime};
+
+    use base64::Engine as _;
+    use sophia_iri::{Iri, IriRef};
 
     use crate::common::SignatureOptions;
-    use crate::util::test::parse_nq;
+    use crate::error::DatasetError;
+    use crate::util::test::{parse_nq, rsa_private_key, sign_and_verify, CREATOR, DATASET};
     use crate::SignOptions;
 
     use super::*;
 
     #[test]
     fn roundtrip() {
-        const DATASET: &str = r#"
+        sign_and_verify(
+            <SignOptions<'_, '_>>::new()
+                .created("2024-01-01T00:00:00Z")
+                .nonce(Some("deadbeef12345678")),
+            &parse_nq(DATASET),
+        );
+    }
+
+    #[test]
+    fn verify_signature() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = sign_rsa_signature_2017(&dataset, &key, creator).unwrap();
+        signature.verify(&dataset, &key).unwrap();
+
+        let tampered = parse_nq(&DATASET.replace("Hello", "Goodbye"));
+        let e = signature.verify(&tampered, &key).unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+    }
+
+    #[test]
+    fn verify_with_key_types() {
+        use std::sync::Arc;
+
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = sign_rsa_signature_2017(&dataset, &key, creator).unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+        let verify = |key: &dyn AsRsaPublicKey| {
+            verify_rsa_signature_2017(&dataset, &options, key, &signature.signature_value)
+        };
+
+        verify(&key).unwrap();
+        verify(&Arc::new(key.to_public_key())).unwrap();
+        verify(&Box::new(key.clone())).unwrap();
+        verify(&rsa::pkcs1v15::VerifyingKey::<sha2::Sha256>::new(
+            key.to_public_key(),
+        ))
+        .unwrap();
+    }
+
+    #[cfg(feature = "insecure-legacy-digest")]
+    #[test]
+    fn legacy_digest() {
+        use rsa::Pkcs1v15Sign;
+        use sha2::{Sha224, Sha256};
+
+        use crate::common::create_verify_hash;
+
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = sign_rsa_signature_2017(&dataset, &key, creator).unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+        verify::verify_rsa_signature_2017_legacy::<Sha256, _, _, _>(
+            &dataset,
+            &options,
+            &key,
+            &signature.signature_value,
+        )
+        .unwrap();
+
+        // Sign like a legacy implementation with a different digest algorithm.
+        let hash = create_verify_hash::<Sha224, _, _>(&dataset, &options, GraphScope::All).unwrap();
+        let legacy = key.sign(Pkcs1v15Sign::new::<Sha224>(), &hash).unwrap();
+        verify::verify_rsa_signature_2017_legacy::<Sha224, _, _, _>(
+            &dataset, &options, &key, &legacy,
+        )
+        .unwrap();
+        let e = verify_rsa_signature_2017(&dataset, &options, &key, &legacy).unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+    }
+
+    #[test]
+    fn max_quads() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let e = <SignOptions<'_, '_>>::new()
+            .max_quads(1)
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap_err();
+        assert!(matches!(
+            e,
+            sign::Error::Dataset(DatasetError::TooManyQuads { limit: 1 })
+        ));
+
+        let signature = <SignOptions<'_, '_>>::new()
+            .max_quads(2)
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+
+        let e = VerifyOptions::new()
+            .max_quads(2)
+            .verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+            .unwrap_err();
+        assert!(matches!(
+            e,
+            verify::Error::Options(DatasetError::TooManyQuads { limit: 2 })
+        ));
+
+        VerifyOptions::new()
+            .max_quads(3)
+            .verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_any() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+        let signature = sign_rsa_signature_2017(&dataset, &key, creator).unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let rotated = rsa::RsaPrivateKey::new(&mut rng, 512)
+            .unwrap()
+            .to_public_key();
+        // A key of a different size doesn't match either.
+        let small = rsa::RsaPrivateKey::new(&mut rng, 256)
+            .unwrap()
+            .to_public_key();
+        let keys = [rotated, small, key.to_public_key()];
+
+        let index =
+            verify_rsa_signature_2017_any(&dataset, &options, &keys, &signature.signature_value)
+                .unwrap();
+        assert_eq!(index, 2);
+
+        let e = verify_rsa_signature_2017_any(
+            &dataset,
+            &options,
+            &keys[..2],
+            &signature.signature_value,
+        )
+        .unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+        let e = verify_rsa_signature_2017_any::<_, _, rsa::RsaPublicKey>(
+            &dataset,
+            &options,
+            &[],
+            &signature.signature_value,
+        )
+        .unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+
+        // Errors other than a mismatching key aren't masked by trying the other keys.
+        let e = VerifyOptions::new()
+            .max_quads(1)
+            .verify_rsa_signature_2017_any(&dataset, &options, &keys, &signature.signature_value)
+            .unwrap_err();
+        assert!(matches!(
+            e,
+            verify::Error::Dataset(DatasetError::TooManyQuads { limit: 1 })
+        ));
+    }
+
+    #[test]
+    fn graph_scope() {
+        const NAMED: &str = r#"
             _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
             _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+            _:b1 <https://www.w3.org/ns/activitystreams#content> "Hello, graph!" _:g0 .
         "#;
-        const N: &[u8] = b"\x00\xa7\x73\xe0\x0f\x71\x95\xeb\xd4\xcb\x36\x25\x2f\x4b\x25\xed\x6e\x3f\x37\xdb\x8a\x3d\x2b\x61\x67\xf2\xea\xe4\x98\x5b\xdb\x56\x5b\x1a\x2c\xa2\xaf\xf7\x65\xcf\xdf\xb5\xa1\x61\x76\x63\x76\x9f\xd2\x64\x18\x05\xd2\x32\x7e\x52\x51\x31\xb2\x8d\x8b\x5a\xa4\x01\x29";
-        const D: &[u8] = b"\x00\x8c\x32\x92\x07\x9c\x1b\xdf\x65\x3b\xf6\x4b\x4f\xbb\x65\x37\xd2\xb4\x0f\x3a\x3a\x15\x58\xba\xa6\xe3\x55\x12\xab\x15\x4d\x20\x90\xae\x53\x71\x9a\xc6\x8b\xd5\xd1\xaa\x94\x63\xbb\x9e\xe2\x72\x90\x10\xb4\x14\xf7\x86\xc4\x03\xaa\x6f\x28\x7d\x1e\x7b\xc7\xa4\x61";
-        const P: &[u8] = b"\x00\xde\x79\xa1\x11\xd2\xac\x22\x86\xc7\xdc\xb2\x03\x01\x05\xcc\x73\x9e\x17\x3c\xef\x9d\x31\x13\x39\x9f\x81\xe6\x68\x85\xdc\xe7\x0d";
-        const Q: &[u8] = b"\x00\xc0\xaf\xa8\x21\x8b\x40\xca\x59\xd5\x00\xd0\x55\x68\xf6\x7f\x35\xd0\x29\xfb\xb6\xb3\x38\x33\xe4\x81\xd6\x6b\x5d\x93\x6b\xfb\x8d";
 
         let dataset = parse_nq(DATASET);
-        let key = RsaPrivateKey::from_components(
-            BigUint::from_bytes_be(N),
-            BigUint::from(65537u64),
-            BigUint::from_bytes_be(D),
-            vec![BigUint::from_bytes_be(P), BigUint::from_bytes_be(Q)],
+        let named = parse_nq(NAMED);
+        let tampered = parse_nq(&NAMED.replace("graph", "tampered graph"));
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = <SignOptions<'_, '_>>::new()
+            .graph_scope(GraphScope::Default)
+            .sign_rsa_signature_2017(&named, &key, creator)
+            .unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+
+        for dataset in [&dataset, &named, &tampered] {
+            VerifyOptions::new()
+                .graph_scope(GraphScope::Default)
+                .verify_rsa_signature_2017(
+                    dataset,
+                    &options,
+                    key.as_ref(),
+                    &signature.signature_value,
+                )
+                .unwrap();
+        }
+        VerifyOptions::new()
+            .verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+            .unwrap();
+        let e =
+            verify_rsa_signature_2017(&named, &options, key.as_ref(), &signature.signature_value)
+                .unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+
+        let signature = sign_rsa_signature_2017(&named, &key, creator).unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+        verify_rsa_signature_2017(&named, &options, key.as_ref(), &signature.signature_value)
+            .unwrap();
+        let e = verify_rsa_signature_2017(
+            &tampered,
+            &options,
+            key.as_ref(),
+            &signature.signature_value,
+        )
+        .unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+    }
+
+    #[test]
+    fn verification_report() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = <SignOptions<'_, '_>>::new()
+            .created("2024-01-01T00:00:00Z")
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+        let created = SystemTime::UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+
+        let report = verify_rsa_signature_2017_report(
+            &dataset,
+            &options,
+            key.as_ref(),
+            &signature.signature_value,
         )
         .unwrap();
-        let creator = Iri::new("https://example.com/#me").unwrap();
+        assert_eq!(
+            report,
+            VerificationReport {
+                crypto_ok: true,
+                within_validity: None,
+                creator: Some(CREATOR.into()),
+                created: Some("2024-01-01T00:00:00Z".into()),
+            }
+        );
+
+        let mut verify_options = VerifyOptions::new();
+        verify_options
+            .max_age(Duration::from_secs(3600))
+            .now(created + Duration::from_secs(1800));
+        let report = verify_options
+            .verify_rsa_signature_2017_report(&dataset, &options, key.as_ref(), &[0; 64])
+            .unwrap();
+        assert!(!report.crypto_ok);
+        assert_eq!(report.within_validity, Some(true));
+        // A truncated signature is reported as a bad signature rather than an error.
+        let report = verify_options
+            .verify_rsa_signature_2017_report(
+                &dataset,
+                &options,
+                key.as_ref(),
+                &signature.signature_value[..32],
+            )
+            .unwrap();
+        assert!(!report.crypto_ok);
+        assert_eq!(report.within_validity, Some(true));
+        verify_options
+            .verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+            .unwrap();
+
+        verify_options.now(created + Duration::from_secs(7200));
+        let report = verify_options
+            .verify_rsa_signature_2017_report(
+                &dataset,
+                &options,
+                key.as_ref(),
+                &signature.signature_value,
+            )
+            .unwrap();
+        assert!(report.crypto_ok);
+        assert_eq!(report.within_validity, Some(false));
+        let e = verify_options
+            .verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+            .unwrap_err();
+        assert!(matches!(
+            e,
+            verify::Error::OutOfValidity { created: Some(_) }
+        ));
+    }
+
+    #[test]
+    fn verify_with_options_dataset() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
 
         let signature = <SignOptions<'_, '_>>::new()
             .created("2024-01-01T00:00:00Z")
@@ -82,18 +440,152 @@ mod tests {
             .sign_rsa_signature_2017(&dataset, &key, creator)
             .unwrap();
 
-        let options = SignatureOptions {
-            created: &signature.created,
-            creator: signature.creator,
-            domain: signature.domain,
-            nonce: signature.nonce.as_deref(),
-        };
-        verify_rsa_signature_2017(
+        let options = parse_nq(&format!(
+            r#"
+                <https://example.com/#sig> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#RsaSignature2017> .
+                <https://example.com/#sig> <http://purl.org/dc/terms/created> "2024-01-01T00:00:00Z"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
+                <https://example.com/#sig> <http://purl.org/dc/terms/creator> <https://example.com/#me> .
+                <https://example.com/#sig> <https://w3id.org/security#nonce> "deadbeef12345678" .
+                <https://example.com/#sig> <https://w3id.org/security#signatureValue> "{}" .
+            "#,
+            base64::engine::general_purpose::STANDARD.encode(&signature.signature_value),
+        ));
+        verify::verify_with_options_dataset(
+            &dataset,
+            &options,
+            Some("https://example.com/#sig"),
+            &signature.signature_value,
+            key.as_ref(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn toxic_options() {
+        use core::fmt::Write as _;
+
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+
+        // Two indistinguishable blank nodes, each linked to more indistinguishable blank nodes than
+        // the canonicalization algorithm is willing to permute.
+        let mut options = String::new();
+        for s in ["a", "b"] {
+            for o in 0..8 {
+                writeln!(options, "_:{s} <https://example.com/p> _:{s}{o} .").unwrap();
+            }
+        }
+        let e = verify::verify_with_options_dataset(
             &dataset,
-            &options.to_dataset(),
+            &parse_nq(&options),
+            None,
+            &[0; 64],
             key.as_ref(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(e, verify::Error::Options(DatasetError::ToxicGraph(_))),
+            "{:?}",
+            e
+        );
+    }
+
+    #[test]
+    fn options_type_triple() {
+        use sophia_api::dataset::MutableDataset;
+        use sophia_api::term::BnodeId;
+
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = <SignOptions<'_, '_>>::new()
+            .created("2024-01-01T00:00:00Z")
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap();
+
+        let mut options = SignatureOptions::from(&signature).to_dataset();
+        options
+            .insert(
+                BnodeId::new_unchecked("b0"),
+                Iri::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+                Iri::new_unchecked("https://w3id.org/security#RsaSignature2017"),
+                None::<&'static sophia_api::term::SimpleTerm<'_>>,
+            )
+            .unwrap();
+
+        // The type triple is hashed as part of the options, unlike the signer's.
+        let e =
+            verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+                .unwrap_err();
+        assert!(matches!(e, verify::Error::Verification(_)));
+
+        verify::verify_with_options_dataset(
+            &dataset,
+            &options,
+            None,
             &signature.signature_value,
+            key.as_ref(),
         )
         .unwrap();
     }
+
+    #[test]
+    fn verify_batch() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = sign_rsa_signature_2017(&dataset, &key, creator).unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+        let bad_signature = vec![0; signature.signature_value.len()];
+
+        let items = [
+            (&dataset, &options, &*signature.signature_value),
+            (&dataset, &options, &*bad_signature),
+            (&dataset, &options, &*signature.signature_value),
+        ];
+
+        let results = verify::verify_batch(items, key.as_ref());
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(verify::Error::Verification(_))));
+        assert!(results[2].is_ok());
+
+        match verify::verify_batch_all(items, key.as_ref()) {
+            Err((1, verify::Error::Verification(_))) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+        verify::verify_batch_all([items[0], items[2]], key.as_ref()).unwrap();
+    }
+
+    #[test]
+    fn expected_type() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let signature = sign_rsa_signature_2017(&dataset, &key, creator).unwrap();
+        let options = SignatureOptions::from(&signature).to_dataset();
+
+        VerifyOptions::new()
+            .expected_type(IriRef::new_unchecked(
+                "https://www.w3.org/ns/activitystreams#Note",
+            ))
+            .verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+            .unwrap();
+
+        let e = VerifyOptions::new()
+            .expected_type(IriRef::new_unchecked(
+                "https://www.w3.org/ns/activitystreams#Create",
+            ))
+            .verify_rsa_signature_2017(&dataset, &options, key.as_ref(), &signature.signature_value)
+            .unwrap_err();
+        match e {
+            verify::Error::UnexpectedDocumentType { found } => assert_eq!(
+                found,
+                [Box::from("https://www.w3.org/ns/activitystreams#Note")]
+            ),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
 }
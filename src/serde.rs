@@ -1,3 +1,7 @@
 mod insert_signature;
+mod signature;
+mod signature_type;
+mod signed_object;
 
 pub use self::insert_signature::InsertSignature;
+pub use self::signed_object::SignedObject;
@@ -18,6 +18,18 @@ pub struct InsertSignature<'a, T> {
 /// A pseudo  `Serializer` that yields an `Ok` iff the "serialized" value equals to `"signature"`.
 struct EqSignature;
 
+/// A pseudo `Serializer` that yields `Ok(true)` iff the "serialized" value is a map or a struct with
+/// a `signature` entry that will be serialized, without serializing the values.
+///
+/// This lets `InsertSignature` report the exact length of the map to length-prefixed formats, in
+/// which replacing an existing entry must not count the new entry twice.
+struct HasSignature;
+
+/// The `SerializeMap` and `SerializeStruct` of `HasSignature`.
+struct HasSignatureEntries {
+    found: bool,
+}
+
 /// A wrapper to reuse the ctors of `de::Error` to construct `ser::Error` (HACK).
 struct SerErrorAsDeError<E>(E);
 
@@ -32,11 +44,14 @@ impl<'a, T: Serialize> Serialize for InsertSignature<'a, T> {
         struct Serializer<'a, S> {
             inner: S,
             signature: &'a Signature<'a>,
+            has_signature: bool,
         }
 
         return self.value.serialize(Serializer {
             inner: serializer,
             signature: self.signature,
+            // Values that don't serialize as a map fail in the actual serialization below.
+            has_signature: self.value.serialize(HasSignature).unwrap_or(false),
         });
 
         struct SerializeMap<'a, S> {
@@ -47,7 +62,7 @@ impl<'a, T: Serialize> Serialize for InsertSignature<'a, T> {
 
         struct SerializeStruct<'a, S> {
             inner: S,
-            signature: Option<&'a Signature<'a>>,
+            signature: &'a Signature<'a>,
         }
 
         /// A pseudo `Visitor` for reusing its default impls as "factories" of `S::Error` (HACK).
@@ -68,7 +83,7 @@ impl<'a, T: Serialize> Serialize for InsertSignature<'a, T> {
 
             fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
                 self.inner
-                    .serialize_map(len.map(|len| len + 1))
+                    .serialize_map(len.map(|len| len + usize::from(!self.has_signature)))
                     .map(|inner| SerializeMap {
                         inner,
                         signature: self.signature,
@@ -82,10 +97,10 @@ impl<'a, T: Serialize> Serialize for InsertSignature<'a, T> {
                 len: usize,
             ) -> Result<Self::SerializeStruct, Self::Error> {
                 self.inner
-                    .serialize_struct(name, len + 1)
+                    .serialize_struct(name, len + usize::from(!self.has_signature))
                     .map(|inner| SerializeStruct {
                         inner,
-                        signature: Some(self.signature),
+                        signature: self.signature,
                     })
             }
 
@@ -345,18 +360,13 @@ impl<'a, T: Serialize> Serialize for InsertSignature<'a, T> {
 
             fn skip_field(&mut self, key: &'static str) -> Result<(), Self::Error> {
                 match key {
-                    "signature" => {
-                        self.signature = None;
-                        Ok(())
-                    }
+                    "signature" => Ok(()),
                     _ => self.inner.skip_field(key),
                 }
             }
 
             fn end(mut self) -> Result<Self::Ok, Self::Error> {
-                if let Some(signature) = self.signature {
-                    self.inner.serialize_field("signature", signature)?;
-                }
+                self.inner.serialize_field("signature", self.signature)?;
                 self.inner.end()
             }
         }
@@ -569,6 +579,219 @@ impl ser::Serializer for EqSignature {
     }
 }
 
+impl ser::Serializer for HasSignature {
+    type Ok = bool;
+    type Error = fmt::Error;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = HasSignatureEntries;
+    type SerializeStruct = HasSignatureEntries;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(HasSignatureEntries { found: false })
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(HasSignatureEntries { found: false })
+    }
+
+    fn serialize_some<T>(self, v: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        v.serialize(self)
+    }
+
+    fn serialize_bool(self, _: bool) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_i128(self, _: i128) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_u128(self, _: u128) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_char(self, _: char) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_str(self, _: &str) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, _: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized,
+    {
+        Err(fmt::Error)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized,
+    {
+        Err(fmt::Error)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(fmt::Error)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(fmt::Error)
+    }
+}
+
+impl ser::SerializeMap for HasSignatureEntries {
+    type Ok = bool;
+    type Error = fmt::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.found |= key.serialize(EqSignature).is_ok();
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, _: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.found)
+    }
+}
+
+impl ser::SerializeStruct for HasSignatureEntries {
+    type Ok = bool;
+    type Error = fmt::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, _: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.found |= key == "signature";
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.found)
+    }
+}
+
 impl<E: ser::Error> de::Error for SerErrorAsDeError<E> {
     fn custom<T: Display>(msg: T) -> Self {
         Self(E::custom(msg))
@@ -602,20 +825,28 @@ mod tests {
     use serde_test::{assert_ser_tokens, assert_ser_tokens_error, Token};
     use sophia_iri::Iri;
 
-    use crate::{Signature, SignatureType};
+    use crate::sign::SignatureEncoding;
+    use crate::{ContextChoice, CreatorKey, Signature, SignatureType};
 
     use super::*;
 
-    const SIGNATURE: Signature<'_> = Signature {
-        _context: (),
-        kind: SignatureType::RsaSignature2017,
-        created: Cow::Borrowed("1990-01-01T00:00:00Z"),
-        creator: Iri::new_unchecked_const("https://example.com/#me"),
-        domain: None,
-        nonce: Some(Cow::Borrowed("deadbeef12345678")),
-        signature_value: Vec::new(),
-    };
-    const SIGNATURE_REF: &Signature<'_> = &SIGNATURE;
+    fn signature() -> Signature<'static> {
+        Signature {
+            context: Default::default(),
+            kind: SignatureType::RsaSignature2017,
+            type_as_array: false,
+            signature_encoding: SignatureEncoding::Base64Standard,
+            created: Cow::Borrowed("1990-01-01T00:00:00Z"),
+            expires: None,
+            creator: Iri::new_unchecked(Cow::Borrowed("https://example.com/#me")),
+            creator_key: CreatorKey::Creator,
+            extra_options: Vec::new(),
+            domain: None,
+            nonce: Some(Cow::Borrowed("deadbeef12345678")),
+            signature_value: Vec::new(),
+            control_char_literals: Vec::new(),
+        }
+    }
     const SIGNATURE_TOKENS: &[Token] = &[
         Token::Struct {
             name: "Signature",
@@ -657,7 +888,8 @@ mod tests {
         }
 
         let value = Test { a: 42 };
-        let wrapper = InsertSignature::new(&value, &SIGNATURE_REF);
+        let signature = signature();
+        let wrapper = InsertSignature::new(&value, &signature);
 
         let mut tokens = vec![
             Token::Struct {
@@ -676,7 +908,8 @@ mod tests {
     #[test]
     fn inserts_new_map_entry() {
         let value = HashMap::<_, _>::from_iter([("a", 42u32)]);
-        let wrapper = InsertSignature::new(&value, &SIGNATURE_REF);
+        let signature = signature();
+        let wrapper = InsertSignature::new(&value, &signature);
 
         let mut tokens = vec![
             Token::Map { len: Some(2) },
@@ -699,15 +932,16 @@ mod tests {
         let value = Test {
             signature: Signature {
                 nonce: Some("12345678deadbeef".into()),
-                ..SIGNATURE
+                ..signature()
             },
         };
-        let wrapper = InsertSignature::new(&value, &SIGNATURE_REF);
+        let signature = signature();
+        let wrapper = InsertSignature::new(&value, &signature);
 
         let mut tokens = vec![
             Token::Struct {
                 name: "Test",
-                len: 2, // FIXME: This should be `1`
+                len: 1,
             },
             Token::Str("signature"),
         ];
@@ -722,30 +956,175 @@ mod tests {
             "signature",
             Signature {
                 nonce: Some("12345678deadbeef".into()),
-                ..SIGNATURE
+                ..signature()
             },
         )]);
-        let wrapper = InsertSignature::new(&value, &SIGNATURE_REF);
+        let signature = signature();
+        let wrapper = InsertSignature::new(&value, &signature);
+
+        let mut tokens = vec![Token::Map { len: Some(1) }, Token::Str("signature")];
+        tokens.extend(SIGNATURE_TOKENS);
+        tokens.push(Token::MapEnd);
+        assert_ser_tokens(&wrapper, &tokens);
+    }
+
+    #[test]
+    fn skipped_struct_field() {
+        #[derive(Serialize)]
+        struct Test {
+            a: u32,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            signature: Option<Signature<'static>>,
+        }
+
+        let value = Test {
+            a: 42,
+            signature: None,
+        };
+        let signature = signature();
+        let wrapper = InsertSignature::new(&value, &signature);
 
         let mut tokens = vec![
-            Token::Map {
-                len: Some(2), // FIXME: This should be `1`
+            Token::Struct {
+                name: "Test",
+                len: 2,
             },
+            Token::Str("a"),
+            Token::U32(42),
             Token::Str("signature"),
         ];
         tokens.extend(SIGNATURE_TOKENS);
-        tokens.push(Token::MapEnd);
+        tokens.push(Token::StructEnd);
         assert_ser_tokens(&wrapper, &tokens);
     }
 
+    #[test]
+    fn cbor_round_trip() {
+        fn round_trip<T: Serialize>(value: &T) -> Vec<(ciborium::Value, ciborium::Value)> {
+            let mut buf = Vec::new();
+            ciborium::ser::into_writer(value, &mut buf).unwrap();
+            let value: ciborium::Value = ciborium::de::from_reader(&*buf).unwrap();
+            value.into_map().unwrap()
+        }
+
+        let signature = Signature {
+            expires: Some("2000-01-01T00:00:00Z".into()),
+            domain: Some("example.com".into()),
+            ..signature()
+        };
+        assert_eq!(round_trip(&signature).len(), 8);
+
+        let value = HashMap::<_, _>::from_iter([("a", 42u32)]);
+        let entries = round_trip(&InsertSignature::new(&value, &signature));
+        assert_eq!(entries.len(), 2);
+
+        let value = HashMap::<_, _>::from_iter([("signature", 42u32)]);
+        let entries = round_trip(&InsertSignature::new(&value, &signature));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, ciborium::Value::from("signature"));
+        assert_eq!(entries[0].1.as_map().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn type_as_array() {
+        let signature = Signature {
+            type_as_array: true,
+            ..signature()
+        };
+
+        let mut tokens = SIGNATURE_TOKENS.to_vec();
+        let i = tokens
+            .iter()
+            .position(|t| *t == Token::Str("type"))
+            .unwrap();
+        tokens.insert(i + 1, Token::Seq { len: Some(1) });
+        tokens.insert(i + 3, Token::SeqEnd);
+        assert_ser_tokens(&signature, &tokens);
+    }
+
+    #[test]
+    fn verification_method() {
+        let signature = Signature {
+            creator_key: CreatorKey::VerificationMethod,
+            ..signature()
+        };
+
+        let mut tokens = SIGNATURE_TOKENS.to_vec();
+        let i = tokens
+            .iter()
+            .position(|t| *t == Token::Str("@vocab"))
+            .unwrap();
+        tokens[i - 1] = Token::Struct {
+            name: "InlineContext",
+            len: 2,
+        };
+        tokens.splice(
+            i + 2..i + 2,
+            [
+                Token::Str("verificationMethod"),
+                Token::Some,
+                Token::Struct {
+                    name: "IdTerm",
+                    len: 2,
+                },
+                Token::Str("@id"),
+                Token::Str("sec:verificationMethod"),
+                Token::Str("@type"),
+                Token::Str("@id"),
+                Token::StructEnd,
+            ],
+        );
+        let i = tokens
+            .iter()
+            .position(|t| *t == Token::Str("creator"))
+            .unwrap();
+        tokens[i] = Token::Str("verificationMethod");
+        assert_ser_tokens(&signature, &tokens);
+    }
+
+    #[test]
+    fn context() {
+        let identity = Signature {
+            context: ContextChoice::IdentityV1,
+            ..signature()
+        };
+        let mut tokens = SIGNATURE_TOKENS.to_vec();
+        let i = tokens
+            .iter()
+            .position(|t| *t == Token::Str("https://w3id.org/security/v1"))
+            .unwrap();
+        tokens[i] = Token::Str("https://w3id.org/identity/v1");
+        assert_ser_tokens(&identity, &tokens);
+
+        let custom = Signature {
+            context: ContextChoice::Custom(vec![
+                "https://example.com/a".into(),
+                "https://example.com/b".into(),
+            ]),
+            ..signature()
+        };
+        let mut tokens = SIGNATURE_TOKENS.to_vec();
+        tokens[i - 1] = Token::Seq { len: Some(3) };
+        tokens.splice(
+            i..=i,
+            [
+                Token::Str("https://example.com/a"),
+                Token::Str("https://example.com/b"),
+            ],
+        );
+        assert_ser_tokens(&custom, &tokens);
+    }
+
     #[test]
     fn errors_if_type_mismatch() {
+        let signature = signature();
+
         let value = 42u32;
-        let wrapper = InsertSignature::new(value, &SIGNATURE_REF);
+        let wrapper = InsertSignature::new(value, &signature);
         assert_ser_tokens_error(&wrapper, &[], "invalid type: integer `42`, expected a map");
 
         let value = [42];
-        let wrapper = InsertSignature::new(value, &SIGNATURE_REF);
+        let wrapper = InsertSignature::new(value, &signature);
         assert_ser_tokens_error(&wrapper, &[], "invalid type: tuple, expected a map");
     }
 }
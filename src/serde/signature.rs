@@ -0,0 +1,144 @@
+use core::fmt::{self, Formatter};
+use std::borrow::Cow;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use serde::de::{
+    self, Deserialize, Deserializer, IntoDeserializer, SeqAccess, Unexpected, Visitor,
+};
+use sophia_iri::Iri;
+
+use crate::sign::{CreatorKey, SignatureEncoding};
+use crate::util::decode_signature_value;
+use crate::{Signature, SignatureType};
+
+impl<'de: 'a, 'a> Deserialize<'de> for Signature<'a> {
+    /// Deserializes a `Signature` from a plain-JSON signature object, without JSON-LD processing.
+    ///
+    /// The `@context` entry is ignored, so this is only suitable when the producer is known to use
+    /// the context that this crate serializes the signature with (or an equivalent one). Unknown
+    /// entries, including the [extra options](crate::SignOptions::extra_option), are ignored as
+    /// well.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Repr<'a> {
+            #[serde(rename = "type")]
+            kind: Type,
+            #[serde(borrow)]
+            created: Cow<'a, str>,
+            #[serde(borrow, default)]
+            expires: Option<Cow<'a, str>>,
+            #[serde(borrow, default)]
+            creator: Option<Cow<'a, str>>,
+            #[serde(borrow, default)]
+            verification_method: Option<Cow<'a, str>>,
+            #[serde(borrow, default)]
+            domain: Option<Cow<'a, str>>,
+            #[serde(borrow, default)]
+            nonce: Option<Cow<'a, str>>,
+            #[serde(borrow)]
+            signature_value: Cow<'a, str>,
+        }
+
+        let repr = Repr::deserialize(deserializer)?;
+
+        let (creator, creator_key) = match (repr.creator, repr.verification_method) {
+            (Some(creator), None) => (creator, CreatorKey::Creator),
+            (None, Some(creator)) => (creator, CreatorKey::VerificationMethod),
+            (Some(_), Some(_)) => {
+                return Err(de::Error::custom(
+                    "both `creator` and `verificationMethod` are present",
+                ))
+            }
+            (None, None) => return Err(de::Error::missing_field("creator")),
+        };
+        let creator = Iri::new(creator)
+            .map_err(|e| de::Error::invalid_value(Unexpected::Str(&e.0), &"an IRI"))?;
+
+        let signature_value = repr.signature_value.as_bytes();
+        let (signature_value, signature_encoding) = match STANDARD.decode(signature_value) {
+            Ok(value) => (value, SignatureEncoding::Base64Standard),
+            Err(_) => decode_signature_value(signature_value)
+                .map(|value| (value, SignatureEncoding::Base64Url))
+                .ok_or_else(|| {
+                    de::Error::invalid_value(
+                        Unexpected::Str(&repr.signature_value),
+                        &"base64 string",
+                    )
+                })?,
+        };
+
+        Ok(Signature {
+            context: Default::default(),
+            kind: repr.kind.kind,
+            type_as_array: repr.kind.as_array,
+            signature_encoding,
+            created: repr.created,
+            expires: repr.expires,
+            creator,
+            creator_key,
+            domain: repr.domain,
+            nonce: repr.nonce,
+            extra_options: Vec::new(),
+            signature_value,
+            control_char_literals: Vec::new(),
+        })
+    }
+}
+
+/// The `type` entry, which may be either a string or an array of a single string.
+struct Type {
+    kind: SignatureType,
+    as_array: bool,
+}
+
+impl<'de> Deserialize<'de> for Type {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TypeVisitor;
+
+        impl<'de> Visitor<'de> for TypeVisitor {
+            type Value = Type;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a signature type or an array of a signature type")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                SignatureType::deserialize(v.into_deserializer()).map(|kind| Type {
+                    kind,
+                    as_array: false,
+                })
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let kind = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::custom(
+                        "multiple signature types are unsupported",
+                    ));
+                }
+                Ok(Type {
+                    kind,
+                    as_array: true,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(TypeVisitor)
+    }
+}
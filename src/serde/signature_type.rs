@@ -0,0 +1,56 @@
+use core::fmt::{self, Formatter};
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+use crate::SignatureType;
+
+impl<'de> Deserialize<'de> for SignatureType {
+    /// Deserializes a `SignatureType` from a string in any of the forms accepted by its `FromStr`
+    /// implementation.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TypeVisitor;
+
+        impl<'de> Visitor<'de> for TypeVisitor {
+            type Value = SignatureType;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a signature type")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                v.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(TypeVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, Token};
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        for ty in [
+            "RsaSignature2017",
+            "sec:RsaSignature2017",
+            "https://w3id.org/security#RsaSignature2017",
+        ] {
+            assert_de_tokens(&SignatureType::RsaSignature2017, &[Token::Str(ty)]);
+        }
+        assert_de_tokens_error::<SignatureType>(
+            &[Token::Str("Ed25519Signature2018")],
+            "invalid value: string \"Ed25519Signature2018\", expected a signature type",
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use serde::Deserialize;
+
+use crate::Signature;
+
+/// A plain-JSON signed object, split into its `signature` entry and the rest of the object.
+///
+/// This is a lightweight counterpart of `json_ld::SignedDocument` that doesn't perform the JSON-LD
+/// expansion, which is fine when the producer is known to use a fixed context. The remaining
+/// entries are deserialized into `T`, e.g., a map type of the JSON library of your choice.
+///
+/// Note that verifying the signature still requires the RDF dataset of the `document`.
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+#[non_exhaustive]
+pub struct SignedObject<'a, T> {
+    /// The entries of the object other than `signature`.
+    #[serde(flatten)]
+    pub document: T,
+    /// The `signature` entry of the object.
+    #[serde(borrow)]
+    pub signature: Signature<'a>,
+}
+
+impl<'a, T> SignedObject<'a, T> {
+    /// Returns the document and the signature.
+    pub fn into_parts(self) -> (T, Signature<'a>) {
+        (self.document, self.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    use crate::sign::CreatorKey;
+    use crate::SignatureType;
+
+    use super::*;
+
+    const DOCUMENT: &str = r#"{
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Note",
+        "content": "Hello, world!",
+        "signature": {
+            "@context": "https://w3id.org/identity/v1",
+            "type": "RsaSignature2017",
+            "created": "2024-01-01T00:00:00Z",
+            "creator": "https://example.com/#me",
+            "nonce": "deadbeef12345678",
+            "signatureValue": "EuukoY4e2Bdp18mQov48Q1E38XetV03SI+DHJOdFm/t8Cz+WP8qbgtM8fg0L9J15B8yyZ7J2+nSeqi2oAuuo7g=="
+        }
+    }"#;
+
+    #[test]
+    fn deserialize() {
+        let (document, signature) =
+            serde_json::from_str::<SignedObject<'_, BTreeMap<String, serde_json::Value>>>(DOCUMENT)
+                .unwrap()
+                .into_parts();
+
+        assert_eq!(
+            document.keys().collect::<Vec<_>>(),
+            ["@context", "content", "type"],
+        );
+        assert_eq!(signature.kind, SignatureType::RsaSignature2017);
+        assert!(!signature.type_as_array);
+        assert!(matches!(
+            signature.created,
+            Cow::Borrowed("2024-01-01T00:00:00Z")
+        ));
+        assert_eq!(signature.creator.as_str(), "https://example.com/#me");
+        assert_eq!(signature.creator_key(), CreatorKey::Creator);
+        assert_eq!(signature.domain, None);
+        assert_eq!(signature.nonce.as_deref(), Some("deadbeef12345678"));
+        assert_eq!(signature.signature_value.len(), 64);
+    }
+
+    #[test]
+    fn verification_method() {
+        let json = DOCUMENT
+            .replace("\"creator\"", "\"verificationMethod\"")
+            .replace("\"RsaSignature2017\"", "[\"RsaSignature2017\"]");
+        let object: SignedObject<'_, serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert!(object.signature.type_as_array);
+        assert_eq!(
+            object.signature.creator_key(),
+            CreatorKey::VerificationMethod
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for (from, to) in [
+            ("\"creator\"", "\"foo\""),
+            ("https://example.com/#me", "not an IRI"),
+            ("\"RsaSignature2017\"", "\"Ed25519Signature2018\""),
+            ("\"EuukoY4e2", "\"!!!"),
+            ("\"nonce\"", "\"verificationMethod\""),
+        ] {
+            let json = DOCUMENT.replace(from, to);
+            assert!(
+                serde_json::from_str::<SignedObject<'_, serde_json::Value>>(&json).is_err(),
+                "{} -> {}",
+                from,
+                to,
+            );
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let object: SignedObject<'_, serde_json::Value> = serde_json::from_str(DOCUMENT).unwrap();
+        let json = serde_json::to_string(&object.signature).unwrap();
+        let signature: Signature<'_> = serde_json::from_str(&json).unwrap();
+        assert_eq!(signature.signature_value, object.signature.signature_value);
+        assert_eq!(signature.creator, object.signature.creator);
+    }
+}
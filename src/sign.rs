@@ -1,55 +1,205 @@
 use core::fmt::{self, Debug, Formatter};
 use std::borrow::Cow;
-use std::time::SystemTime;
 
 use base64::display::Base64Display;
-use either::Either;
-use rand_core::{CryptoRng, RngCore};
-use rsa::{Pkcs1v15Sign, RsaPrivateKey};
-use sha2::Sha256;
+#[cfg(any(feature = "sign", feature = "verify"))]
+use sophia_api::dataset::Dataset;
 use sophia_api::dataset::SetDataset;
+use sophia_api::term::{BnodeId, SimpleTerm};
+use sophia_inmem::dataset::LightDataset;
 use sophia_iri::Iri;
 
-use crate::common::{create_verify_hash, SignatureOptions};
+#[cfg(any(feature = "sign", feature = "serde"))]
+use crate::common::consts;
+use crate::common::{hash_dataset_hex, SignatureOptions};
 use crate::error::DatasetError;
-use crate::util::{format_iso8601_time, gen_nonce, NeverRng};
 use crate::SignatureType;
+#[cfg(feature = "sign")]
+use {
+    core::time::Duration,
+    either::Either,
+    rand_chacha::ChaCha20Rng,
+    rand_core::{CryptoRng, CryptoRngCore, RngCore, SeedableRng},
+    rsa::RsaPrivateKey,
+    sha2::{digest::Output, Digest},
+    sophia_api::term::{Term, TermKind},
+    std::{borrow::Borrow, time::SystemTime},
+    time::OffsetDateTime,
+};
 
+#[cfg(feature = "sign")]
+use crate::common::{
+    canonicalize_options, check_quad_count, create_verify_hash, find_control_char_literals,
+    is_empty_in_scope, GraphScope,
+};
+#[cfg(feature = "sign")]
+use crate::error::KeyError;
+#[cfg(feature = "sign")]
+use crate::suite::{RsaSignature2017, SignatureSuite};
+use crate::util::Base64Debug;
+#[cfg(feature = "sign")]
+use crate::util::{self, format_iso8601_datetime, format_iso8601_time, gen_nonce, NeverRng};
+
+#[cfg(feature = "sign")]
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct SignOptions<'sig, 'this, R = NeverRng> {
     /// The date and time of the signature generation in the ISO 8601 format.
-    pub created: Option<&'sig str>,
+    pub created: Option<Cow<'sig, str>>,
     /// The operational domain of the signature.
     pub domain: Option<&'sig str>,
     /// The nonce value of the signature.
     pub nonce: Option<Option<&'sig str>>,
     /// The random number generator used during the signature generation.
     pub rng: Option<&'this mut R>,
+    /// The maximum number of quads allowed in the dataset to be signed.
+    pub max_quads: Option<usize>,
+    /// Whether to sign a dataset with no quads in the signed graphs.
+    pub allow_empty: bool,
+    /// The seed of the RNG used when no RNG is set, for reproducible signatures.
+    pub seed: Option<[u8; 32]>,
+    /// Whether to report the literals containing control characters that RDFC 1.0 and URDNA2015
+    /// canonicalize differently.
+    pub warn_control_chars: bool,
+    /// The graphs of the dataset to be signed.
+    pub graph_scope: GraphScope,
+    /// The number of digits of fractional seconds in the datetimes formatted by this crate.
+    pub created_precision: u8,
+    /// The time to live of the signature, from which the expiration datetime is computed.
+    pub expires_in: Option<Duration>,
+    /// The property used to identify the signing key.
+    pub creator_key: CreatorKey,
+    /// Additional signature options, as pairs of predicates and objects.
+    pub extra_options: Vec<(Iri<&'sig str>, SimpleTerm<'sig>)>,
+    /// The JSON-LD context of the serialized signature.
+    #[cfg(feature = "serde")]
+    pub context: ContextChoice,
+    /// Whether to serialize the `type` of the signature as an array.
+    #[cfg(feature = "serde")]
+    pub type_as_array: bool,
+    /// The encoding of the serialized `signatureValue`.
+    #[cfg(feature = "serde")]
+    pub signature_encoding: SignatureEncoding,
+    /// Whether to reject a JSON document that already has a `signature` entry instead of replacing
+    /// the entry.
+    #[cfg(feature = "serde_json")]
+    pub reject_existing_signature: bool,
+}
+
+/// The property of the signature options that identifies the signing key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CreatorKey {
+    /// The `creator` (`dc:creator`) property of the legacy Linked Data Signatures.
+    #[default]
+    Creator,
+    /// The `verificationMethod` (`sec:verificationMethod`) property of the newer Linked Data
+    /// Proofs.
+    VerificationMethod,
+}
+
+/// The JSON-LD context that a serialized signature refers to in its `@context` entry.
+///
+/// The context URLs are followed by an inline context that this crate relies on to make the `type`
+/// and `verificationMethod` terms expand properly.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContextChoice {
+    /// The Security Vocabulary context, `https://w3id.org/security/v1`.
+    #[default]
+    SecurityV1,
+    /// The context that the Linked Data Signatures spec used, `https://w3id.org/identity/v1`.
+    ///
+    /// The URL is now a dead link, but some legacy verifiers expect it and have it cached, like
+    /// [`PreloadedLoader`](crate::json_ld::loader::PreloadedLoader) of this crate does.
+    IdentityV1,
+    /// The given context URLs.
+    ///
+    /// The contexts must define the terms of the signature options, including the `sec` prefix,
+    /// and the verifiers must be able to load them.
+    Custom(Vec<String>),
+}
+
+/// The encoding of the `signatureValue` of a serialized signature.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureEncoding {
+    /// The standard base64 alphabet with padding, which the existing implementations expect.
+    #[default]
+    Base64Standard,
+    /// The URL-safe base64 alphabet without padding.
+    Base64Url,
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Signature<'a> {
-    #[cfg_attr(
-        feature = "serde",
-        serde(rename = "@context", serialize_with = "serialize_context")
-    )]
-    pub(crate) _context: (),
-    #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub kind: SignatureType,
+    #[cfg(feature = "serde")]
+    pub(crate) context: ContextChoice,
+    #[cfg(feature = "serde")]
+    pub(crate) type_as_array: bool,
+    #[cfg(feature = "serde")]
+    pub(crate) signature_encoding: SignatureEncoding,
+    /// The date and time of the signature generation in the ISO 8601 format.
+    ///
+    /// This is always the exact literal that was hashed during the signature generation, whether
+    /// it was given by [`SignOptions::created`] or generated automatically, so it can be used
+    /// as-is to verify the signature.
     pub created: Cow<'a, str>,
-    pub creator: Iri<&'a str>,
-    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub domain: Option<&'a str>,
-    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    /// The date and time of the signature expiration in the ISO 8601 format, if any.
+    pub expires: Option<Cow<'a, str>>,
+    pub creator: Iri<Cow<'a, str>>,
+    pub(crate) creator_key: CreatorKey,
+    /// Additional signature options signed along with the known ones, as pairs of predicates and
+    /// objects.
+    pub extra_options: Vec<(Iri<Cow<'a, str>>, SimpleTerm<'a>)>,
+    pub domain: Option<Cow<'a, str>>,
     pub nonce: Option<Cow<'a, str>>,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_bytes_base64"))]
     pub signature_value: Vec<u8>,
+    pub(crate) control_char_literals: Vec<String>,
 }
 
-pub type Error<DE> = DatasetError<DE>;
+/// Error while signing a dataset.
+#[cfg(feature = "sign")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error<DE> {
+    /// The dataset or the signature options couldn't be canonicalized.
+    #[error(transparent)]
+    Dataset(#[from] DatasetError<DE>),
+    /// The signing key can't be used with the signature suite.
+    #[error(transparent)]
+    Key(#[from] KeyError),
+    /// The expiration datetime couldn't be computed from the `created` datetime, which was either
+    /// malformed or too far in the future.
+    #[error("Unable to compute the expiration from `created`: {created:?}")]
+    BadExpires {
+        /// The `created` datetime of the signature options.
+        created: Box<str>,
+    },
+    /// The dataset had no quads to be signed. See [`SignOptions::allow_empty`].
+    #[error("The dataset to be signed has no quads")]
+    EmptyDataset,
+    /// Signing needed randomness but no RNG was available, i.e., neither an RNG nor a seed was set
+    /// with [`SignOptions::rng`] or [`SignOptions::deterministic`], and the `thread-rng` feature
+    /// was disabled or the target was WebAssembly.
+    #[error("No RNG is set; set one with `SignOptions::rng` or enable the `thread-rng` feature")]
+    NoRng,
+    /// The `created` datetime was needed but not set with [`SignOptions::created`], and the
+    /// current time was unavailable, i.e., the target was WebAssembly with the `wasm` feature.
+    #[error("The current time is unavailable; set the datetime with `SignOptions::created`")]
+    NoClock,
+    /// An option set with [`SignOptions::extra_option`] had a reserved predicate or an object
+    /// other than an IRI, a blank node or a literal.
+    #[error("Invalid extra signature option: {predicate}")]
+    BadExtraOption {
+        /// The predicate of the option.
+        predicate: Box<str>,
+    },
+}
 
+#[cfg(feature = "sign")]
 impl<'sig, 'this, R> SignOptions<'sig, 'this, R>
 where
     R: RngCore + CryptoRng,
@@ -62,9 +212,51 @@ where
     /// Sets the datetime of the signature generation.
     ///
     /// By default, the datetime is automatically set during the signature operation. This method
-    /// shouldn't be used in production!
+    /// shouldn't be used in production, except on WebAssembly targets where the current time is
+    /// unavailable (see the [crate-level documentation](crate#webassembly)).
+    ///
+    /// The string is signed as is, so it may have any precision, e.g., nanoseconds, regardless of
+    /// [`created_precision`](Self::created_precision).
     pub fn created(&mut self, created: impl Into<Option<&'sig str>>) -> &mut Self {
-        self.created = created.into();
+        self.created = created.into().map(Cow::Borrowed);
+        self
+    }
+
+    /// Sets the datetime of the signature generation from a [`SystemTime`].
+    ///
+    /// The time is formatted in the ISO 8601 format with the
+    /// [`created_precision`](Self::created_precision) set at the time of the call, e.g.,
+    /// `2024-01-01T00:00:00.000Z`, just like the automatically set datetime.
+    pub fn created_time(&mut self, created: SystemTime) -> &mut Self {
+        self.created = Some(Cow::Owned(format_iso8601_time(
+            created,
+            self.created_precision,
+        )));
+        self
+    }
+
+    /// Sets the datetime of the signature generation from an [`OffsetDateTime`].
+    ///
+    /// The datetime is formatted in the ISO 8601 format with the
+    /// [`created_precision`](Self::created_precision) set at the time of the call, e.g.,
+    /// `2024-01-01T00:00:00.000Z`, preserving its UTC offset.
+    pub fn created_datetime(&mut self, created: OffsetDateTime) -> &mut Self {
+        self.created = Some(Cow::Owned(format_iso8601_datetime(
+            created,
+            self.created_precision,
+        )));
+        self
+    }
+
+    /// Sets the number of digits of fractional seconds, from 0 to 9, in the datetimes that this
+    /// `SignOptions` formats, i.e., the automatically set `created` datetime, the `expires`
+    /// datetime and the ones given to [`created_time`](Self::created_time) and
+    /// [`created_datetime`](Self::created_datetime) after this call. Larger values are treated as 9.
+    ///
+    /// Defaults to 3, i.e., millisecond precision. The verification hashes the datetimes as they
+    /// appear in the signature, so this doesn't affect the interoperability of the signatures.
+    pub fn created_precision(&mut self, decimal_digits: u8) -> &mut Self {
+        self.created_precision = decimal_digits;
         self
     }
 
@@ -78,80 +270,558 @@ where
     ///
     /// By default, the nonce value is automatically set during the signature operation. This method
     /// shouldn't be used in production!
+    ///
+    /// `Some(None)` omits the nonce and `None` restores the default behavior. See also
+    /// [`no_nonce`](Self::no_nonce) and [`auto_nonce`](Self::auto_nonce), which are more
+    /// self-explanatory.
     pub fn nonce(&mut self, nonce: impl Into<Option<Option<&'sig str>>>) -> &mut Self {
         self.nonce = nonce.into();
         self
     }
 
+    /// Omits the nonce from the signature.
+    ///
+    /// The nonce is optional in the `RsaSignature2017` suite, and some strict verifiers reject or
+    /// mishandle signatures that have one. Call this once on a `SignOptions` that is reused across
+    /// signature operations to omit the nonce from all of them.
+    ///
+    /// This is equivalent to `nonce(Some(None))`.
+    pub fn no_nonce(&mut self) -> &mut Self {
+        self.nonce = Some(None);
+        self
+    }
+
+    /// Makes the nonce value automatically generated during the signature operation, which is the
+    /// default.
+    ///
+    /// This is equivalent to `nonce(None)`.
+    pub fn auto_nonce(&mut self) -> &mut Self {
+        self.nonce = None;
+        self
+    }
+
+    /// Makes the signature expire after the `duration` from its [`created`](Self::created)
+    /// datetime, by setting the `expires` property (`sec:expiration`).
+    ///
+    /// The expiration datetime is formatted just like the automatically set `created` datetime. If
+    /// the `created` datetime is set explicitly, it must be a valid ISO 8601 datetime, or the
+    /// signature operation fails with [`Error::BadExpires`].
+    ///
+    /// Note that the verification in this crate doesn't check the expiration datetime (yet). See
+    /// [`VerifyOptions::max_age`](crate::VerifyOptions::max_age) for a verifier-side limit.
+    pub fn expires_in(&mut self, duration: impl Into<Option<Duration>>) -> &mut Self {
+        self.expires_in = duration.into();
+        self
+    }
+
     /// Sets a custom random number generator to be used during the signature generation.
     ///
-    /// By default, [`rand::thread_rng`] is used.
+    /// By default, `rand::thread_rng` is used if the `thread-rng` feature is enabled. Otherwise,
+    /// the RNG must be set before signing. The same applies on WebAssembly targets if the `wasm`
+    /// feature is enabled.
     pub fn rng(&mut self, rng: impl Into<Option<&'this mut R>>) -> &mut Self {
         self.rng = rng.into();
         self
     }
 
+    /// Sets the maximum number of quads allowed in the dataset to be signed.
+    ///
+    /// If set, the signature operation fails with [`DatasetError::TooManyQuads`] (wrapped in
+    /// [`Error::Dataset`]) before canonicalizing a dataset with more quads than the limit.
+    pub fn max_quads(&mut self, max_quads: impl Into<Option<usize>>) -> &mut Self {
+        self.max_quads = max_quads.into();
+        self
+    }
+
+    /// Sets whether to sign a dataset that has no quads in the graphs of the
+    /// [`graph_scope`](Self::graph_scope).
+    ///
+    /// Such a signature only covers the signature options, which is seldom intended and typically
+    /// results from signing a document that failed to parse or expand to any quads. So, by
+    /// default, the signature operation fails with [`Error::EmptyDataset`].
+    pub fn allow_empty(&mut self, allow_empty: bool) -> &mut Self {
+        self.allow_empty = allow_empty;
+        self
+    }
+
+    /// Makes the signature operation use an RNG seeded with the `seed` unless an RNG is set with
+    /// [`rng`](Self::rng), so that the same inputs yield the same signature.
+    ///
+    /// The RNG is used to generate the nonce value and for the RSA blinding. Note that the datetime
+    /// of the signature generation still defaults to the current time, so you need to set it with
+    /// [`created`](Self::created) as well to get a reproducible signature.
+    ///
+    /// **This is only meant for test fixtures and documentation examples.** Don't use this in
+    /// production, where the nonce values need to be unpredictable!
+    pub fn deterministic(&mut self, seed: impl Into<Option<[u8; 32]>>) -> &mut Self {
+        self.seed = seed.into();
+        self
+    }
+
+    /// Sets whether to check the signed dataset for literals containing control characters.
+    ///
+    /// This library uses the RDFC 1.0 canonicalization algorithm, which escapes some control
+    /// characters differently from URDNA2015, so a signature of a dataset containing such
+    /// characters may not verify with other implementations. If set, the offending literals are
+    /// reported by [`Signature::control_char_literals`].
+    pub fn warn_control_chars(&mut self, warn_control_chars: bool) -> &mut Self {
+        self.warn_control_chars = warn_control_chars;
+        self
+    }
+
+    /// Sets the graphs of the dataset to be signed.
+    ///
+    /// Defaults to [`GraphScope::All`]. Note that the signature verifies only if the same scope is
+    /// used for the verification.
+    pub fn graph_scope(&mut self, graph_scope: GraphScope) -> &mut Self {
+        self.graph_scope = graph_scope;
+        self
+    }
+
+    /// Sets the property used to identify the signing key, i.e., whether the signature has a
+    /// `creator` or a `verificationMethod`.
+    ///
+    /// Defaults to [`CreatorKey::Creator`]. The property is part of the signed options, so the
+    /// choice affects the signature value as well as its serialized form.
+    pub fn creator_key(&mut self, creator_key: CreatorKey) -> &mut Self {
+        self.creator_key = creator_key;
+        self
+    }
+
+    /// Adds a signature option other than the ones this crate models, which is signed along with
+    /// them.
+    ///
+    /// When serialized, the option is written with the full IRI of the `predicate` as the key, so
+    /// that it expands correctly regardless of the context.
+    ///
+    /// # Errors
+    ///
+    /// Signing fails with [`Error::BadExtraOption`] if the `predicate` is one of the options that
+    /// are set from the other methods or removed before hashing, i.e., `rdf:type`, `dc:created`,
+    /// `dc:creator`, `sec:expiration`, `sec:domain`, `sec:nonce`, `sec:verificationMethod`,
+    /// `sec:signatureValue`, `sec:normalizationAlgorithm` or `sec:canonicalizationAlgorithm`, or if
+    /// the `object` is not an IRI, a blank node or a literal.
+    pub fn extra_option(
+        &mut self,
+        predicate: Iri<&'sig str>,
+        object: SimpleTerm<'sig>,
+    ) -> &mut Self {
+        self.extra_options.push((predicate, object));
+        self
+    }
+
+    /// Sets the JSON-LD context that the serialized signature refers to.
+    ///
+    /// Defaults to [`ContextChoice::SecurityV1`]. The context is not part of the signed options,
+    /// so the choice only affects the serialized form of the signature.
+    #[cfg(feature = "serde")]
+    pub fn context(&mut self, context: ContextChoice) -> &mut Self {
+        self.context = context;
+        self
+    }
+
+    /// Sets whether to serialize the `type` of the signature as an array, i.e.,
+    /// `"type": ["RsaSignature2017"]` instead of `"type": "RsaSignature2017"`.
+    ///
+    /// Both forms are equivalent in JSON-LD, but some strict consumers expect the array form.
+    #[cfg(feature = "serde")]
+    pub fn type_as_array(&mut self, type_as_array: bool) -> &mut Self {
+        self.type_as_array = type_as_array;
+        self
+    }
+
+    /// Sets whether [`sign_json`](crate::json_ld::sign_json) rejects a document that already has a
+    /// `signature` entry with
+    /// [`SignJsonError::ExistingSignature`](crate::json_ld::SignJsonError::ExistingSignature).
+    ///
+    /// By default, the existing entry is removed before signing and replaced with the new
+    /// signature. It is never signed over either way, since that would change the hash compared to
+    /// the document without it. This only affects `sign_json`:
+    /// [`InsertSignature`](crate::serde::InsertSignature) always replaces an existing entry, as it
+    /// serializes a signature made separately from the document.
+    #[cfg(feature = "serde_json")]
+    pub fn reject_existing_signature(&mut self, reject_existing_signature: bool) -> &mut Self {
+        self.reject_existing_signature = reject_existing_signature;
+        self
+    }
+
+    /// Sets the encoding of the `signatureValue` of the serialized signature.
+    ///
+    /// Defaults to [`SignatureEncoding::Base64Standard`]. The JSON-LD parser of this crate accepts
+    /// either encoding, but other implementations may not.
+    #[cfg(feature = "serde")]
+    pub fn signature_encoding(&mut self, signature_encoding: SignatureEncoding) -> &mut Self {
+        self.signature_encoding = signature_encoding;
+        self
+    }
+
     /// Signs the given `dataset` with the `RsaSignature2017` algorithm.
     ///
-    /// See also [`Signature::sign_rsa_signature_2017`] function, which is a shorthand for this
-    /// method.
-    pub fn sign_rsa_signature_2017<D>(
+    /// See also `sign_rsa_signature_2017` function, which is a shorthand for this method.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::NoRng`] if no RNG is set with [`rng`](Self::rng) and the `thread-rng`
+    /// feature is disabled or the target is WebAssembly.
+    ///
+    /// On WebAssembly targets with the `wasm` feature enabled, fails with [`Error::NoClock`] if no
+    /// datetime is set with [`created`](Self::created).
+    pub fn sign_rsa_signature_2017<D, C>(
         &mut self,
         dataset: &D,
         key: &RsaPrivateKey,
-        creator: Iri<&'sig str>,
-    ) -> Result<Signature<'sig>, DatasetError<D::Error>>
+        creator: Iri<C>,
+    ) -> Result<Signature<'sig>, Error<D::Error>>
     where
         D: SetDataset,
+        C: Borrow<str> + Into<Cow<'sig, str>>,
     {
-        let nonce = match self.nonce {
-            Some(Some(nonce)) => Some(Cow::Borrowed(nonce)),
-            Some(None) => None,
-            None => {
-                let owned = if let Some(rng) = self.rng.as_deref_mut() {
-                    gen_nonce(rng)
-                } else {
-                    gen_nonce(&mut rand::thread_rng())
-                };
-                Some(Cow::Owned(owned))
-            }
+        self.sign::<RsaSignature2017, D, C>(dataset, key, creator)
+    }
+
+    /// Lazily signs each of the `datasets` with the `RsaSignature2017` algorithm as the returned
+    /// iterator advances.
+    ///
+    /// The options and the RNG are shared across the datasets. In particular, the RNG seeded by
+    /// [`deterministic`](Self::deterministic) is seeded once for the whole iteration rather than for
+    /// each dataset, so that the datasets get distinct nonces while the sequence of the signatures
+    /// stays reproducible.
+    ///
+    /// See also [`sign_all`] function, which is a shorthand for this method.
+    ///
+    /// # Errors
+    ///
+    /// The iterator yields errors and panics under the same conditions as
+    /// [`sign_rsa_signature_2017`](Self::sign_rsa_signature_2017).
+    pub fn sign_all<'a, I, D, C>(
+        &'a mut self,
+        datasets: I,
+        key: &'a RsaPrivateKey,
+        creator: Iri<C>,
+    ) -> impl Iterator<Item = Result<Signature<'sig>, Error<D::Error>>> + use<'a, 'sig, 'this, R, I, D, C>
+    where
+        I: IntoIterator<Item = D>,
+        D: SetDataset,
+        C: Borrow<str> + Into<Cow<'sig, str>> + Clone,
+    {
+        let mut seeded = self.seed.map(ChaCha20Rng::from_seed);
+        datasets.into_iter().map(move |dataset| {
+            self.sign_seeded::<RsaSignature2017, D, C>(
+                &dataset,
+                key,
+                creator.clone(),
+                seeded.as_mut(),
+            )
+        })
+    }
+
+    /// Signs the given `dataset` with the signature suite `S`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::NoRng`] if no RNG is set with [`rng`](Self::rng) and the `thread-rng`
+    /// feature is disabled or the target is WebAssembly.
+    ///
+    /// On WebAssembly targets with the `wasm` feature enabled, fails with [`Error::NoClock`] if no
+    /// datetime is set with [`created`](Self::created).
+    pub fn sign<S, D, C>(
+        &mut self,
+        dataset: &D,
+        key: &S::SigningKey,
+        creator: Iri<C>,
+    ) -> Result<Signature<'sig>, Error<D::Error>>
+    where
+        S: SignatureSuite,
+        D: SetDataset,
+        C: Borrow<str> + Into<Cow<'sig, str>>,
+    {
+        let mut seeded = self.seed.map(ChaCha20Rng::from_seed);
+        self.sign_seeded::<S, D, C>(dataset, key, creator, seeded.as_mut())
+    }
+
+    /// Signs the `dataset` like [`sign`](Self::sign), drawing the randomness from the `seeded` RNG
+    /// if no RNG is set.
+    fn sign_seeded<S, D, C>(
+        &mut self,
+        dataset: &D,
+        key: &S::SigningKey,
+        creator: Iri<C>,
+        mut seeded: Option<&mut ChaCha20Rng>,
+    ) -> Result<Signature<'sig>, Error<D::Error>>
+    where
+        S: SignatureSuite,
+        D: SetDataset,
+        C: Borrow<str> + Into<Cow<'sig, str>>,
+    {
+        S::check_signing_key(key)?;
+
+        let creator = creator.map_unchecked(Into::into);
+
+        let nonce = self.resolve_nonce(seeded.as_deref_mut())?;
+        let prepared = self.prepare::<S, D>(dataset, &creator, nonce)?;
+
+        let control_char_literals = if self.warn_control_chars {
+            let mut literals = find_control_char_literals(dataset, self.graph_scope)
+                .map_err(DatasetError::Dataset)?;
+            // `LightDataset` returns error only when inserting quads.
+            literals.extend(
+                find_control_char_literals(&prepared.options.dataset, GraphScope::All).unwrap(),
+            );
+            literals
+        } else {
+            Vec::new()
         };
 
-        let created = self
-            .created
-            .map(Cow::Borrowed)
-            .unwrap_or_else(|| Cow::Owned(format_iso8601_time(SystemTime::now())));
+        let signature_value = Self::with_rng(self.rng.as_deref_mut(), seeded, |mut rng| {
+            S::sign(key, &mut rng, &prepared.to_be_signed)
+        })
+        .ok_or(Error::NoRng)?;
 
-        let options = SignatureOptions {
-            created: &created,
+        Ok(Signature {
+            kind: S::TYPE,
+            created: prepared.options.created,
+            expires: prepared.options.expires,
             creator,
-            domain: self.domain,
-            nonce: nonce.as_deref(),
-        };
-        let to_be_signed = create_verify_hash(dataset, &options.to_dataset())
-            // `LightDataset` returns error only when inserting quads.
-            .map_err(Either::unwrap_left)?;
+            creator_key: self.creator_key,
+            extra_options: prepared.options.extra_options,
+            domain: self.domain.map(Cow::Borrowed),
+            nonce: prepared.options.nonce,
+            signature_value,
+            control_char_literals,
+            #[cfg(feature = "serde")]
+            context: self.context.clone(),
+            #[cfg(feature = "serde")]
+            type_as_array: self.type_as_array,
+            #[cfg(feature = "serde")]
+            signature_encoding: self.signature_encoding,
+        })
+    }
+
+    /// Checks that the `key` can sign with the `RsaSignature2017` suite.
+    ///
+    /// The signature operations perform the check as well and fail with [`Error::Key`], but this
+    /// lets you validate a key once up front, e.g., when loading it. Note that the check only
+    /// rejects keys that can't sign at all. Keys below 2048 bits are insecure nevertheless.
+    pub fn check_key(&self, key: &RsaPrivateKey) -> Result<(), KeyError> {
+        RsaSignature2017::check_signing_key(key)
+    }
+
+    /// Returns the output of the Create Verify Hash Algorithm for the given `dataset` with the
+    /// `RsaSignature2017` suite, i.e., the hash that
+    /// [`sign_rsa_signature_2017`](Self::sign_rsa_signature_2017) would sign, without signing it.
+    ///
+    /// This is useful to tell whether an interoperability issue lies in the canonicalization or in
+    /// the signature algorithm. Set the [`created`](Self::created) datetime and the
+    /// [`nonce`](Self::nonce) explicitly, or the hash covers values that are unknown to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::NoRng`] if no nonce is set and no RNG is available, like
+    /// [`sign_rsa_signature_2017`](Self::sign_rsa_signature_2017).
+    pub fn create_verify_hash_rsa_signature_2017<D, C>(
+        &mut self,
+        dataset: &D,
+        creator: Iri<C>,
+    ) -> Result<Vec<u8>, Error<D::Error>>
+    where
+        D: SetDataset,
+        C: Borrow<str> + Into<Cow<'sig, str>>,
+    {
+        self.create_verify_hash::<RsaSignature2017, D, C>(dataset, creator)
+    }
+
+    /// Returns the output of the Create Verify Hash Algorithm for the given `dataset` with the
+    /// signature suite `S`.
+    ///
+    /// See [`create_verify_hash_rsa_signature_2017`](Self::create_verify_hash_rsa_signature_2017)
+    /// for the details.
+    pub fn create_verify_hash<S, D, C>(
+        &mut self,
+        dataset: &D,
+        creator: Iri<C>,
+    ) -> Result<Vec<u8>, Error<D::Error>>
+    where
+        S: SignatureSuite,
+        D: SetDataset,
+        C: Borrow<str> + Into<Cow<'sig, str>>,
+    {
+        let creator = creator.map_unchecked(Into::into);
+        let mut seeded = self.seed.map(ChaCha20Rng::from_seed);
+        let nonce = self.resolve_nonce(seeded.as_mut())?;
+        let prepared = self.prepare::<S, D>(dataset, &creator, nonce)?;
+        Ok(prepared.to_be_signed.to_vec())
+    }
+
+    /// Returns the canonical N-Quads serialization of the signature options that
+    /// [`create_verify_hash_rsa_signature_2017`](Self::create_verify_hash_rsa_signature_2017)
+    /// would hash.
+    ///
+    /// Along with [`canonicalize_document`](crate::canonicalize_document), this reveals the exact
+    /// input of the hash. The options don't depend on the document, so this doesn't look at it.
+    /// The same caveats as `create_verify_hash_rsa_signature_2017` apply to the `created` datetime
+    /// and the nonce: an automatic nonce is drawn from the RNG, so it matches the one of a later
+    /// signature only with [`deterministic`](Self::deterministic).
+    pub fn canonicalize_options<C>(
+        &mut self,
+        creator: Iri<C>,
+    ) -> Result<String, Error<<LightDataset as Dataset>::Error>>
+    where
+        C: Borrow<str> + Into<Cow<'sig, str>>,
+    {
+        let creator = creator.map_unchecked(Into::into);
+        let mut seeded = self.seed.map(ChaCha20Rng::from_seed);
+        let nonce = self.resolve_nonce(seeded.as_mut())?;
+        let options = self.resolve_options(&creator, nonce)?;
+        // The extra options may contain blank nodes, so this may fail as a toxic graph.
+        Ok(canonicalize_options(&options.dataset)?)
+    }
 
-        let padding = Pkcs1v15Sign::new::<Sha256>();
-        let signature_value = if let Some(rng) = self.rng.as_deref_mut() {
-            key.sign_with_rng(rng, padding, &to_be_signed).unwrap()
+    /// Returns the nonce to sign with, generating one if the nonce is automatic.
+    fn resolve_nonce<DE>(
+        &mut self,
+        seeded: Option<&mut ChaCha20Rng>,
+    ) -> Result<Option<Cow<'sig, str>>, Error<DE>> {
+        match self.nonce {
+            Some(Some(nonce)) => Ok(Some(Cow::Borrowed(nonce))),
+            Some(None) => Ok(None),
+            None => Self::with_rng(self.rng.as_deref_mut(), seeded, |rng| gen_nonce(rng))
+                .map(|nonce| Some(Cow::Owned(nonce)))
+                .ok_or(Error::NoRng),
+        }
+    }
+
+    /// Calls `f` with the RNG set with [`rng`](Self::rng), the one seeded by
+    /// [`deterministic`](Self::deterministic) or the default one, in this order of preference.
+    ///
+    /// Returns `None` if there is no RNG to call `f` with.
+    fn with_rng<T>(
+        rng: Option<&mut R>,
+        seeded: Option<&mut ChaCha20Rng>,
+        f: impl FnOnce(&mut dyn CryptoRngCore) -> T,
+    ) -> Option<T> {
+        if let Some(rng) = rng {
+            Some(f(rng))
+        } else if let Some(seeded) = seeded {
+            Some(f(seeded))
         } else {
-            key.sign_with_rng(&mut rand::thread_rng(), padding, &to_be_signed)
-                .unwrap()
+            with_default_rng(f)
+        }
+    }
+
+    /// Resolves the signature options and computes the hash to be signed with the suite `S`.
+    fn prepare<S, D>(
+        &self,
+        dataset: &D,
+        creator: &Iri<Cow<'sig, str>>,
+        nonce: Option<Cow<'sig, str>>,
+    ) -> Result<Prepared<'sig, S::Digest>, Error<D::Error>>
+    where
+        S: SignatureSuite,
+        D: SetDataset,
+    {
+        let options = self.resolve_options(creator, nonce)?;
+        if let Some(limit) = self.max_quads {
+            check_quad_count(dataset, limit)?;
+        }
+        if !self.allow_empty
+            && is_empty_in_scope(dataset, self.graph_scope).map_err(DatasetError::Dataset)?
+        {
+            return Err(Error::EmptyDataset);
+        }
+
+        let to_be_signed =
+            create_verify_hash::<S::Digest, _, _>(dataset, &options.dataset, self.graph_scope)
+                // `LightDataset` returns error only when inserting quads.
+                .map_err(Either::unwrap_left)?;
+
+        Ok(Prepared {
+            options,
+            to_be_signed,
+        })
+    }
+
+    /// Resolves the signature options, without looking at the dataset to be signed.
+    fn resolve_options<DE>(
+        &self,
+        creator: &Iri<Cow<'sig, str>>,
+        nonce: Option<Cow<'sig, str>>,
+    ) -> Result<ResolvedOptions<'sig>, Error<DE>> {
+        if let Some((predicate, _)) = self
+            .extra_options
+            .iter()
+            .find(|(predicate, object)| !is_valid_extra_option(predicate.as_str(), object))
+        {
+            return Err(Error::BadExtraOption {
+                predicate: predicate.as_str().into(),
+            });
+        }
+
+        // The same `created` value is hashed and returned, so that it verifies as-is.
+        let created = match self.created {
+            Some(ref created) => created.clone(),
+            None => Cow::Owned(format_iso8601_time(
+                util::now().ok_or(Error::NoClock)?,
+                self.created_precision,
+            )),
         };
 
-        Ok(Signature {
-            kind: SignatureType::RsaSignature2017,
+        let expires = self
+            .expires_in
+            .map(|duration| {
+                util::parse_iso8601_datetime(&created)
+                    .zip(time::Duration::try_from(duration).ok())
+                    .and_then(|(created, duration)| created.checked_add(duration))
+                    .map(|expires| {
+                        Cow::Owned(format_iso8601_datetime(expires, self.created_precision))
+                    })
+                    .ok_or_else(|| Error::BadExpires {
+                        created: (*created).into(),
+                    })
+            })
+            .transpose()?;
+
+        let extra_options: Vec<_> = self
+            .extra_options
+            .iter()
+            .map(|(predicate, object)| (predicate.map_unchecked(Cow::Borrowed), object.clone()))
+            .collect();
+
+        let dataset = SignatureOptions::new(&created, creator.as_ref())
+            .with_expires(expires.as_deref())
+            .with_creator_key(self.creator_key)
+            .with_domain(self.domain)
+            .with_nonce(nonce.as_deref())
+            .with_extra_options(&extra_options)
+            .to_dataset();
+
+        Ok(ResolvedOptions {
             created,
-            creator,
-            domain: self.domain,
+            expires,
             nonce,
-            signature_value,
-            _context: (),
+            extra_options,
+            dataset,
         })
     }
 }
 
+/// The signature options resolved by `SignOptions::prepare`, along with the hash to be signed.
+#[cfg(feature = "sign")]
+struct Prepared<'sig, H: Digest> {
+    options: ResolvedOptions<'sig>,
+    to_be_signed: Output<H>,
+}
+
+/// The signature options resolved by `SignOptions::resolve_options`.
+#[cfg(feature = "sign")]
+struct ResolvedOptions<'sig> {
+    created: Cow<'sig, str>,
+    expires: Option<Cow<'sig, str>>,
+    nonce: Option<Cow<'sig, str>>,
+    extra_options: Vec<(Iri<Cow<'sig, str>>, SimpleTerm<'sig>)>,
+    dataset: LightDataset,
+}
+
+#[cfg(feature = "sign")]
 impl<'sig, 'this, R> Default for SignOptions<'sig, 'this, R> {
     fn default() -> Self {
         SignOptions {
@@ -159,36 +829,186 @@ impl<'sig, 'this, R> Default for SignOptions<'sig, 'this, R> {
             domain: None,
             nonce: None,
             rng: None,
+            max_quads: None,
+            allow_empty: false,
+            seed: None,
+            warn_control_chars: false,
+            graph_scope: GraphScope::All,
+            created_precision: 3,
+            expires_in: None,
+            creator_key: CreatorKey::Creator,
+            extra_options: Vec::new(),
+            #[cfg(feature = "serde")]
+            context: ContextChoice::SecurityV1,
+            #[cfg(feature = "serde")]
+            type_as_array: false,
+            #[cfg(feature = "serde")]
+            signature_encoding: SignatureEncoding::Base64Standard,
+            #[cfg(feature = "serde_json")]
+            reject_existing_signature: false,
         }
     }
 }
 
 impl<'a> Signature<'a> {
-    pub fn options<'b>() -> SignOptions<'a, 'b> {
-        SignOptions::new()
+    /// Returns the lexical forms of the literals of the signed dataset and the signature options
+    /// that contain control characters that RDFC 1.0 and URDNA2015 canonicalize differently.
+    ///
+    /// This is always empty unless [`SignOptions::warn_control_chars`] is set.
+    pub fn control_char_literals(&self) -> &[String] {
+        &self.control_char_literals
     }
-}
 
-impl<'a> Debug for Signature<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        struct Base64Debug<'a>(&'a [u8]);
+    /// Returns the property identifying the signing key, as set by [`SignOptions::creator_key`].
+    pub fn creator_key(&self) -> CreatorKey {
+        self.creator_key
+    }
+
+    /// Returns the signature options dataset whose hash was signed, with the options node labeled
+    /// `bnode_label`, or `b0` if `None`.
+    ///
+    /// The label doesn't affect the signature because the dataset is canonicalized before hashing,
+    /// but setting it, e.g., to `c14n0`, can make it easier to diff the dataset against the output
+    /// of other implementations.
+    pub fn options_dataset<'b>(
+        &self,
+        bnode_label: impl Into<Option<BnodeId<&'b str>>>,
+    ) -> LightDataset {
+        SignatureOptions::from(self).to_dataset_with_bnode(bnode_label.into())
+    }
+
+    /// Returns whether the signature `options` dataset and the `signature_value` describe the same
+    /// signature as this one, e.g., after a round trip through a JSON-LD serialization.
+    ///
+    /// The options are compared by their canonical forms, so blank node labels and the order of the
+    /// quads don't matter, and neither do the serialization details such as the `@context`. Like
+    /// in the verification, the `options` must not contain the `rdf:type` or `signatureValue`
+    /// properties of the signature node.
+    ///
+    /// See also [`json_ld::Signature::semantically_eq`](crate::json_ld::Signature::semantically_eq)
+    /// to compare against a parsed signature.
+    pub fn semantically_eq<O: SetDataset>(
+        &self,
+        options: &O,
+        signature_value: &[u8],
+    ) -> Result<bool, DatasetError<O::Error>> {
+        if self.signature_value != signature_value {
+            return Ok(false);
+        }
+        // `LightDataset` never fails to yield its quads and a dataset with a single subject node
+        // is never deemed toxic by the canonicalization algorithm.
+        let this = hash_dataset_hex(&self.options_dataset(None)).unwrap();
+        Ok(this == hash_dataset_hex(options)?)
+    }
+
+    /// Verifies this signature for the signed `dataset` with the `key`, e.g., to check a signature
+    /// before publishing it.
+    ///
+    /// This is a shorthand for [`verify_rsa_signature_2017`](crate::verify_rsa_signature_2017)
+    /// with the [`options_dataset`](Self::options_dataset). Use
+    /// [`VerifyOptions`](crate::VerifyOptions) instead for a signature made with a
+    /// [`GraphScope`] other than the default.
+    #[cfg(feature = "verify")]
+    pub fn verify<D, K>(
+        &self,
+        dataset: &D,
+        key: &K,
+    ) -> Result<(), crate::verify::Error<D::Error, <LightDataset as Dataset>::Error>>
+    where
+        D: SetDataset,
+        K: crate::AsRsaPublicKey + ?Sized,
+    {
+        let options = self.options_dataset(None);
+        match self.kind {
+            SignatureType::RsaSignature2017 => {
+                crate::verify_rsa_signature_2017(dataset, &options, key, &self.signature_value)
+            }
+        }
+    }
 
-        impl<'a> Debug for Base64Debug<'a> {
+    /// Returns the signature value encoded in the standard base64 alphabet with padding, as it
+    /// appears in the `signatureValue` property.
+    pub fn signature_value_base64(&self) -> String {
+        Base64Display::new(
+            &self.signature_value,
+            &base64::engine::general_purpose::STANDARD,
+        )
+        .to_string()
+    }
+
+    /// Returns the signature value encoded in the URL-safe base64 alphabet without padding.
+    pub fn signature_value_base64url(&self) -> String {
+        Base64Display::new(
+            &self.signature_value,
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+        )
+        .to_string()
+    }
+
+    /// Writes the JSON `document` with this signature inserted as its `signature` entry to the
+    /// `writer`, replacing an existing `signature` entry.
+    ///
+    /// Unlike inserting the signature into the document and printing the result, this doesn't
+    /// clone the document nor build the whole output in memory. The output is written in many small
+    /// chunks, so the `writer` should be buffered. The document is printed compactly without a
+    /// trailing newline.
+    ///
+    /// Returns an error of the [`InvalidInput`](std::io::ErrorKind::InvalidInput) kind if the
+    /// `document` is not a JSON object.
+    #[cfg(all(feature = "json-ld", feature = "serde"))]
+    pub fn write_signed_document_to<M, W>(
+        &self,
+        document: &json_syntax::Value<M>,
+        mut writer: W,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use json_syntax::Print as _;
+
+        struct StringLiteral<'a>(&'a str);
+
+        impl fmt::Display for StringLiteral<'_> {
             fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-                write!(
-                    f,
-                    "\"{}\"",
-                    Base64Display::new(self.0, &base64::engine::general_purpose::STANDARD)
-                )
+                json_syntax::print::string_literal(self.0, f)
             }
         }
 
+        let object = document.as_object().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "expected a JSON object")
+        })?;
+        // `Signature` always serializes to a JSON object.
+        let signature = json_syntax::to_value(self).unwrap();
+
+        writer.write_all(b"{")?;
+        for entry in object.iter().filter(|entry| &**entry.key != "signature") {
+            write!(
+                writer,
+                "{}:{},",
+                StringLiteral(&entry.key),
+                entry.value.compact_print(),
+            )?;
+        }
+        write!(writer, "\"signature\":{}}}", signature.compact_print())
+    }
+
+    #[cfg(feature = "thread-rng")]
+    pub fn options<'b>() -> SignOptions<'a, 'b> {
+        SignOptions::new()
+    }
+}
+
+impl<'a> Debug for Signature<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Signature")
             .field("kind", &self.kind)
             .field("created", &self.created)
+            .field("expires", &self.expires)
             .field("creator", &self.creator)
+            .field("creator_key", &self.creator_key)
             .field("domain", &self.domain)
             .field("nonce", &self.nonce)
+            .field("extra_options", &self.extra_options)
             .field("signature_value", &Base64Debug(&self.signature_value))
             .finish()
     }
@@ -197,53 +1017,721 @@ impl<'a> Debug for Signature<'a> {
 /// Shorthand for `<SignOptions>::new().sign_rsa_signature_2017(…)`.
 ///
 /// See also [`SignOptions::sign_rsa_signature_2017`].
-pub fn sign_rsa_signature_2017<'a, D>(
+#[cfg(feature = "thread-rng")]
+pub fn sign_rsa_signature_2017<'a, D, C>(
     dataset: &D,
     key: &RsaPrivateKey,
-    creator: Iri<&'a str>,
+    creator: Iri<C>,
 ) -> Result<Signature<'a>, Error<D::Error>>
 where
     D: SetDataset,
+    C: Borrow<str> + Into<Cow<'a, str>>,
 {
     <SignOptions<'_, '_>>::new().sign_rsa_signature_2017(dataset, key, creator)
 }
 
-#[cfg(feature = "serde")]
-fn serialize_context<S>(_: &(), serializer: S) -> Result<S::Ok, S::Error>
+/// Shorthand for `<SignOptions>::new().sign_all(…)`.
+///
+/// See also [`SignOptions::sign_all`].
+#[cfg(feature = "thread-rng")]
+pub fn sign_all<'a, I, D, C>(
+    datasets: I,
+    key: &RsaPrivateKey,
+    creator: Iri<C>,
+) -> impl Iterator<Item = Result<Signature<'a>, Error<D::Error>>> + use<'a, '_, I, D, C>
 where
-    S: serde::Serializer,
+    I: IntoIterator<Item = D>,
+    D: SetDataset,
+    C: Borrow<str> + Into<Cow<'a, str>> + Clone,
 {
-    use serde::ser::SerializeSeq;
+    let mut options = <SignOptions<'_, '_>>::new();
+    datasets
+        .into_iter()
+        .map(move |dataset| options.sign_rsa_signature_2017(&dataset, key, creator.clone()))
+}
+
+/// Returns whether a signature option is allowed in [`SignOptions::extra_options`], i.e., whether
+/// the `predicate` is not one of the options that are set from the other options or removed before
+/// hashing, and the `object` can be an object of a signature option.
+#[cfg(feature = "sign")]
+fn is_valid_extra_option(predicate: &str, object: &SimpleTerm<'_>) -> bool {
+    let reserved = [
+        consts::TYPE,
+        consts::CREATED,
+        consts::CREATOR,
+        consts::EXPIRATION,
+        consts::DOMAIN,
+        consts::NONCE,
+        consts::VERIFICATION_METHOD,
+        consts::SIGNATURE_VALUE,
+        consts::NORMALIZATION_ALGORITHM,
+        consts::CANONICALIZATION_ALGORITHM,
+    ];
+    !reserved.iter().any(|reserved| **reserved == *predicate)
+        && matches!(
+            object.kind(),
+            TermKind::Iri | TermKind::BlankNode | TermKind::Literal
+        )
+}
+
+#[cfg(all(
+    feature = "thread-rng",
+    not(all(feature = "wasm", target_arch = "wasm32"))
+))]
+fn with_default_rng<T>(f: impl FnOnce(&mut dyn CryptoRngCore) -> T) -> Option<T> {
+    Some(f(&mut rand::thread_rng()))
+}
+
+/// There is no default RNG without the `thread-rng` feature or on WebAssembly targets.
+#[cfg(all(
+    feature = "sign",
+    not(all(
+        feature = "thread-rng",
+        not(all(feature = "wasm", target_arch = "wasm32"))
+    ))
+))]
+fn with_default_rng<T>(_: impl FnOnce(&mut dyn CryptoRngCore) -> T) -> Option<T> {
+    None
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for Signature<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{SerializeMap, SerializeStruct};
+
+        // The extra options have dynamic keys, which only a map can have. Otherwise, serialize as
+        // a struct, which some formats handle more efficiently.
+        enum State<S: serde::Serializer> {
+            Struct(S::SerializeStruct),
+            Map(S::SerializeMap),
+        }
+
+        impl<S: serde::Serializer> State<S> {
+            fn field<T>(&mut self, key: &'static str, value: &T) -> Result<(), S::Error>
+            where
+                T: serde::Serialize + ?Sized,
+            {
+                match self {
+                    State::Struct(state) => state.serialize_field(key, value),
+                    State::Map(state) => state.serialize_entry(key, value),
+                }
+            }
+
+            fn optional_field<T>(
+                &mut self,
+                key: &'static str,
+                value: &Option<T>,
+            ) -> Result<(), S::Error>
+            where
+                T: serde::Serialize,
+            {
+                match (self, value) {
+                    (this, Some(_)) => this.field(key, value),
+                    (State::Struct(state), None) => state.skip_field(key),
+                    (State::Map(_), None) => Ok(()),
+                }
+            }
+        }
 
-    #[derive(serde::Serialize)]
-    struct InlineContext {
-        #[serde(rename = "@vocab")]
-        vocab: &'static str,
+        let len = 5
+            + usize::from(self.expires.is_some())
+            + usize::from(self.domain.is_some())
+            + usize::from(self.nonce.is_some());
+        let mut state = if self.extra_options.is_empty() {
+            State::<S>::Struct(serializer.serialize_struct("Signature", len)?)
+        } else {
+            State::Map(serializer.serialize_map(Some(len + self.extra_options.len()))?)
+        };
+        state.field("@context", &Context(&self.context, self.creator_key))?;
+        state.field("type", &Type(&self.kind, self.type_as_array))?;
+        state.field("created", &self.created)?;
+        state.optional_field("expires", &self.expires)?;
+        match self.creator_key {
+            CreatorKey::Creator => state.field("creator", &self.creator)?,
+            CreatorKey::VerificationMethod => state.field("verificationMethod", &self.creator)?,
+        }
+        state.optional_field("domain", &self.domain)?;
+        state.optional_field("nonce", &self.nonce)?;
+        state.field(
+            "signatureValue",
+            &Base64(&self.signature_value, self.signature_encoding),
+        )?;
+        match state {
+            State::Struct(state) => state.end(),
+            State::Map(mut state) => {
+                for (predicate, object) in &self.extra_options {
+                    // A full IRI as a key expands to itself regardless of the context.
+                    state.serialize_entry(predicate.as_str(), &ObjectTerm(object))?;
+                }
+                state.end()
+            }
+        }
     }
+}
 
-    let mut seq = serializer.serialize_seq(Some(2))?;
-    // The LD Signatures spec used the context URL of <https://w3id.org/identity/v1>, which is now a
-    // dead link. Although many implementations treat the context as already retrieved, the terms
-    // used by LD Signatures are defined in the Security Vocabulary context as well, and I think
-    // it's safer to use the latter.
-    seq.serialize_element("https://w3id.org/security/v1")?;
-    seq.serialize_element(&InlineContext {
-        // Required to make the `"type": "RsaSignature2017"` entry properly expand to
-        // `"type": "sec:RsaSignature2017"`, although the LD Signatures algorithms explicitly ignore
-        // the `type` term and many plain-JSON processors doesn't seem to care about it either.
-        vocab: "sec:",
-    })?;
-    seq.end()
+/// An object of an extra option serialized as a JSON-LD value or node object.
+#[cfg(feature = "serde")]
+struct ObjectTerm<'a>(&'a SimpleTerm<'a>);
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ObjectTerm<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{Error as _, SerializeMap};
+
+        match *self.0 {
+            SimpleTerm::Iri(ref iri) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("@id", iri.as_str())?;
+                map.end()
+            }
+            SimpleTerm::BlankNode(ref id) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("@id", &format_args!("_:{}", id.as_str()))?;
+                map.end()
+            }
+            SimpleTerm::LiteralDatatype(ref value, ref datatype)
+                if datatype.as_str() == consts::STRING =>
+            {
+                serializer.serialize_str(value)
+            }
+            SimpleTerm::LiteralDatatype(ref value, ref datatype) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("@value", &**value)?;
+                map.serialize_entry("@type", datatype.as_str())?;
+                map.end()
+            }
+            SimpleTerm::LiteralLanguage(ref value, ref tag) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("@value", &**value)?;
+                map.serialize_entry("@language", tag.as_str())?;
+                map.end()
+            }
+            _ => Err(S::Error::custom(
+                "the object of a signature option must be an IRI, a blank node or a literal",
+            )),
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
-fn serialize_bytes_base64<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
-where
-    T: AsRef<[u8]>,
-    S: serde::Serializer,
-{
-    serializer.collect_str(&Base64Display::new(
-        bytes.as_ref(),
-        &base64::engine::general_purpose::STANDARD,
-    ))
+struct Context<'a>(&'a ContextChoice, CreatorKey);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Context<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        #[derive(serde::Serialize)]
+        struct InlineContext {
+            #[serde(rename = "@vocab")]
+            vocab: &'static str,
+            #[serde(rename = "verificationMethod", skip_serializing_if = "Option::is_none")]
+            verification_method: Option<IdTerm>,
+        }
+
+        #[derive(serde::Serialize)]
+        struct IdTerm {
+            #[serde(rename = "@id")]
+            id: &'static str,
+            #[serde(rename = "@type")]
+            kind: &'static str,
+        }
+
+        // The LD Signatures spec used the context URL of <https://w3id.org/identity/v1>, which is
+        // now a dead link. Although many implementations treat the context as already retrieved,
+        // the terms used by LD Signatures are defined in the Security Vocabulary context as well,
+        // and I think it's safer to use the latter by default.
+        let len = match *self.0 {
+            ContextChoice::Custom(ref urls) => urls.len(),
+            _ => 1,
+        };
+        let mut seq = serializer.serialize_seq(Some(len + 1))?;
+        match *self.0 {
+            ContextChoice::SecurityV1 => seq.serialize_element("https://w3id.org/security/v1")?,
+            ContextChoice::IdentityV1 => seq.serialize_element("https://w3id.org/identity/v1")?,
+            ContextChoice::Custom(ref urls) => {
+                for url in urls {
+                    seq.serialize_element(url)?;
+                }
+            }
+        }
+        seq.serialize_element(&InlineContext {
+            // Required to make the `"type": "RsaSignature2017"` entry properly expand to
+            // `"type": "sec:RsaSignature2017"`, although the LD Signatures algorithms explicitly
+            // ignore the `type` term and many plain-JSON processors doesn't seem to care about it
+            // either.
+            vocab: "sec:",
+            // The Security Vocabulary v1 context doesn't define the term, and `@vocab` alone would
+            // expand its value to a string literal rather than an IRI.
+            verification_method: match self.1 {
+                CreatorKey::Creator => None,
+                CreatorKey::VerificationMethod => Some(IdTerm {
+                    id: "sec:verificationMethod",
+                    kind: "@id",
+                }),
+            },
+        })?;
+        seq.end()
+    }
+}
+
+// Serialized in place of the `kind` field, so that the `type` entry can be an array.
+#[cfg(feature = "serde")]
+struct Type<'a>(&'a SignatureType, bool);
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for Type<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        if self.1 {
+            let mut seq = serializer.serialize_seq(Some(1))?;
+            seq.serialize_element(self.0)?;
+            seq.end()
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct Base64<'a>(&'a [u8], SignatureEncoding);
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for Base64<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+
+        let engine = match self.1 {
+            SignatureEncoding::Base64Standard => &STANDARD,
+            SignatureEncoding::Base64Url => &URL_SAFE_NO_PAD,
+        };
+        serializer.collect_str(&Base64Display::new(self.0, engine))
+    }
+}
+
+#[cfg(all(test, feature = "thread-rng", feature = "verify"))]
+mod tests {
+    use crate::util::test::{parse_nq, rsa_private_key, sign_and_verify, CREATOR, DATASET};
+    use crate::{canonicalize_document, verify, VerifyOptions};
+
+    use super::*;
+
+    #[test]
+    fn create_verify_hash() {
+        use sha2::Digest as _;
+
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let mut options = <SignOptions<'_, '_>>::new();
+        options
+            .created("2024-01-01T00:00:00Z")
+            .nonce(Some("deadbeef12345678"));
+        let hash = options
+            .create_verify_hash_rsa_signature_2017(&dataset, creator)
+            .unwrap();
+
+        // The hash covers the canonical forms of the options and the document.
+        let digest_hex = |nquads: String| hex::encode(sha2::Sha256::digest(nquads));
+        let input = digest_hex(options.canonicalize_options(creator).unwrap())
+            + &digest_hex(canonicalize_document(&dataset, GraphScope::All).unwrap());
+        assert_eq!(hash, sha2::Sha256::digest(input).as_slice());
+
+        let signature = options
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap();
+
+        key.to_public_key()
+            .verify(
+                rsa::Pkcs1v15Sign::new::<sha2::Sha256>(),
+                &hash,
+                &signature.signature_value,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn owned_creator() {
+        fn sign(dataset: &impl SetDataset) -> Signature<'static> {
+            let creator = Iri::new(String::from(CREATOR)).unwrap();
+            sign_rsa_signature_2017(dataset, &rsa_private_key(), creator).unwrap()
+        }
+
+        let dataset = parse_nq(DATASET);
+        let signature = sign(&dataset);
+        assert_eq!(signature.creator.as_str(), CREATOR);
+        assert!(matches!(signature.creator.clone().unwrap(), Cow::Owned(_)));
+        signature.verify(&dataset, &rsa_private_key()).unwrap();
+    }
+
+    #[test]
+    fn auto_created() {
+        let signature = sign_and_verify(&mut <SignOptions<'_, '_>>::new(), &parse_nq(DATASET));
+        assert!(matches!(signature.created, Cow::Owned(_)));
+        assert!(signature.nonce.is_some());
+    }
+
+    #[test]
+    fn deterministic() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let sign = |seed| {
+            <SignOptions<'_, '_>>::new()
+                .created("2024-01-01T00:00:00Z")
+                .deterministic(seed)
+                .sign_rsa_signature_2017(&dataset, &key, creator)
+                .unwrap()
+        };
+        let signature = sign([0; 32]);
+        let same = sign([0; 32]);
+        assert_eq!(same.nonce, signature.nonce);
+        assert_eq!(same.signature_value, signature.signature_value);
+        let different = sign([1; 32]);
+        assert_ne!(different.nonce, signature.nonce);
+    }
+
+    #[test]
+    fn sign_all() {
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+        let datasets = || {
+            [DATASET, &DATASET.replace("world", "outbox")]
+                .into_iter()
+                .map(parse_nq)
+                .collect::<Vec<_>>()
+        };
+        let sign = || {
+            <SignOptions<'_, '_>>::new()
+                .created("2024-01-01T00:00:00Z")
+                .deterministic([0; 32])
+                .sign_all(datasets(), &key, creator)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+        let signatures = sign();
+        assert_eq!(signatures.len(), 2);
+        // The seeded RNG is shared across the datasets rather than reseeded for each of them.
+        assert_ne!(signatures[0].nonce, signatures[1].nonce);
+        for (signature, same) in signatures.iter().zip(sign()) {
+            assert_eq!(same.nonce, signature.nonce);
+            assert_eq!(same.signature_value, signature.signature_value);
+        }
+
+        let signatures = super::sign_all(datasets(), &key, creator)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for (dataset, signature) in datasets().iter().zip(&signatures) {
+            signature.verify(dataset, &key).unwrap();
+        }
+    }
+
+    #[test]
+    fn signature_value_base64() {
+        let mut signature = sign_and_verify(&mut <SignOptions<'_, '_>>::new(), &parse_nq(DATASET));
+        signature.signature_value = vec![0xfb, 0xff, 0xbf, 0xfe];
+        assert_eq!(signature.signature_value_base64(), "+/+//g==");
+        assert_eq!(signature.signature_value_base64url(), "-_-__g");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn signature_encoding() {
+        let signature = sign_and_verify(
+            <SignOptions<'_, '_>>::new().signature_encoding(SignatureEncoding::Base64Url),
+            &parse_nq(DATASET),
+        );
+        let json = serde_json::to_value(&signature).unwrap();
+        assert_eq!(
+            json["signatureValue"],
+            *signature.signature_value_base64url()
+        );
+
+        let parsed = <Signature<'_> as ::serde::Deserialize>::deserialize(&json).unwrap();
+        assert_eq!(parsed.signature_value, signature.signature_value);
+        assert_eq!(parsed.signature_encoding, SignatureEncoding::Base64Url);
+    }
+
+    #[test]
+    fn no_nonce() {
+        let dataset = parse_nq(DATASET);
+
+        let mut options = <SignOptions<'_, '_>>::new();
+        options.no_nonce();
+        let signature = sign_and_verify(&mut options, &dataset);
+        assert_eq!(signature.nonce, None);
+        // The setting persists across signature operations.
+        let second = sign_and_verify(&mut options, &dataset);
+        assert_eq!(second.nonce, None);
+    }
+
+    #[test]
+    fn created_time() {
+        let dataset = parse_nq(DATASET);
+
+        let signature = sign_and_verify(
+            <SignOptions<'_, '_>>::new()
+                .created_time(SystemTime::UNIX_EPOCH + Duration::from_millis(1_704_067_200_500)),
+            &dataset,
+        );
+        assert_eq!(signature.created, "2024-01-01T00:00:00.500Z");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(1_704_067_200)
+            .unwrap()
+            .to_offset(time::UtcOffset::from_hms(9, 0, 0).unwrap());
+        let signature = sign_and_verify(
+            <SignOptions<'_, '_>>::new().created_datetime(datetime),
+            &dataset,
+        );
+        assert_eq!(signature.created, "2024-01-01T09:00:00.000+09:00");
+    }
+
+    #[test]
+    fn created_precision() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+        let time = SystemTime::UNIX_EPOCH + Duration::from_nanos(1_704_067_200_123_456_789);
+
+        for (decimal_digits, created) in [
+            (0, "2024-01-01T00:00:00Z"),
+            (1, "2024-01-01T00:00:00.1Z"),
+            (6, "2024-01-01T00:00:00.123456Z"),
+            (9, "2024-01-01T00:00:00.123456789Z"),
+            (42, "2024-01-01T00:00:00.123456789Z"),
+        ] {
+            let signature = <SignOptions<'_, '_>>::new()
+                .created_precision(decimal_digits)
+                .created_time(time)
+                .expires_in(Duration::from_secs(60))
+                .sign_rsa_signature_2017(&dataset, &key, creator)
+                .unwrap();
+            assert_eq!(signature.created, created);
+            assert_eq!(
+                signature.expires.as_deref(),
+                Some(&*created.replace("00:00:00", "00:01:00"))
+            );
+        }
+
+        // A `created` string is signed and verified as is, whatever its precision.
+        let signature = <SignOptions<'_, '_>>::new()
+            .created("2024-01-01T00:00:00.123456789+09:00")
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap();
+        assert_eq!(signature.created, "2024-01-01T00:00:00.123456789+09:00");
+        VerifyOptions::new()
+            .max_age(Duration::MAX)
+            .verify_rsa_signature_2017(
+                &dataset,
+                &SignatureOptions::from(&signature).to_dataset(),
+                key.as_ref(),
+                &signature.signature_value,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn expires_in() {
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+
+        let signature = sign_and_verify(
+            <SignOptions<'_, '_>>::new()
+                .created("2024-01-01T09:00:00+09:00")
+                .expires_in(Duration::from_secs(24 * 60 * 60)),
+            &dataset,
+        );
+        assert_eq!(
+            signature.expires.as_deref(),
+            Some("2024-01-02T09:00:00.000+09:00")
+        );
+
+        // The expiration datetime is signed.
+        let mut tampered = signature;
+        tampered.expires = Some(Cow::Borrowed("2124-01-02T09:00:00.000+09:00"));
+        assert!(matches!(
+            tampered.verify(&dataset, &key),
+            Err(verify::Error::Verification(_))
+        ));
+
+        let e = <SignOptions<'_, '_>>::new()
+            .created("yesterday")
+            .expires_in(Duration::from_secs(60))
+            .sign_rsa_signature_2017(&dataset, &key, Iri::new(CREATOR).unwrap())
+            .unwrap_err();
+        assert!(matches!(e, Error::BadExpires { created } if &*created == "yesterday"));
+    }
+
+    #[test]
+    fn warn_control_chars() {
+        let dataset = parse_nq(
+            r#"
+                _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+                _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello,\tworld!" .
+                _:b0 <https://www.w3.org/ns/activitystreams#summary> "Hello,\u0007world!" .
+                _:b0 <https://www.w3.org/ns/activitystreams#name> "Hello,\r\nworld!" .
+                _:b1 <https://www.w3.org/ns/activitystreams#content> "Hello,\u000Cworld!" _:g0 .
+            "#,
+        );
+
+        let signature = sign_and_verify(&mut <SignOptions<'_, '_>>::new(), &dataset);
+        assert!(signature.control_char_literals().is_empty());
+
+        let signature = sign_and_verify(
+            <SignOptions<'_, '_>>::new().warn_control_chars(true),
+            &dataset,
+        );
+        // Newlines are escaped alike by both algorithms, unlike tabs.
+        let mut literals = signature.control_char_literals().to_vec();
+        literals.sort_unstable();
+        assert_eq!(
+            literals,
+            ["Hello,\u{7}world!", "Hello,\tworld!", "Hello,\u{c}world!"]
+        );
+
+        // The named graphs are not reported unless they are signed.
+        let signature = <SignOptions<'_, '_>>::new()
+            .warn_control_chars(true)
+            .graph_scope(GraphScope::Default)
+            .sign_rsa_signature_2017(&dataset, &rsa_private_key(), Iri::new(CREATOR).unwrap())
+            .unwrap();
+        let mut literals = signature.control_char_literals().to_vec();
+        literals.sort_unstable();
+        assert_eq!(literals, ["Hello,\u{7}world!", "Hello,\tworld!"]);
+    }
+
+    #[test]
+    fn key_too_small() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let key = RsaPrivateKey::new(&mut rng, 256).unwrap();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let options = <SignOptions<'_, '_>>::new();
+        let expected = KeyError::ModulusTooSmall {
+            bits: 256,
+            minimum: 489,
+        };
+        assert_eq!(options.check_key(&key), Err(expected.clone()));
+        let e = <SignOptions<'_, '_>>::new()
+            .sign_rsa_signature_2017(&parse_nq(DATASET), &key, creator)
+            .unwrap_err();
+        assert!(matches!(e, Error::Key(e) if e == expected));
+
+        options.check_key(&rsa_private_key()).unwrap();
+    }
+
+    #[test]
+    fn empty_dataset() {
+        const NAMED_ONLY: &str = r#"
+            _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, graph!" _:g0 .
+        "#;
+
+        let empty = LightDataset::new();
+        let named_only = parse_nq(NAMED_ONLY);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let e = sign_rsa_signature_2017(&empty, &key, creator).unwrap_err();
+        assert!(matches!(e, Error::EmptyDataset));
+        let e = <SignOptions<'_, '_>>::new()
+            .graph_scope(GraphScope::Default)
+            .sign_rsa_signature_2017(&named_only, &key, creator)
+            .unwrap_err();
+        assert!(matches!(e, Error::EmptyDataset));
+        sign_and_verify(&mut <SignOptions<'_, '_>>::new(), &named_only);
+
+        sign_and_verify(<SignOptions<'_, '_>>::new().allow_empty(true), &empty);
+    }
+
+    #[test]
+    fn bad_extra_option() {
+        use sophia_api::term::{IriRef, VarName};
+
+        let dataset = parse_nq(DATASET);
+        let key = rsa_private_key();
+        let creator = Iri::new(CREATOR).unwrap();
+
+        let e = <SignOptions<'_, '_>>::new()
+            .extra_option(
+                Iri::new_unchecked("https://w3id.org/security#nonce"),
+                SimpleTerm::LiteralDatatype(
+                    "deadbeef12345678".into(),
+                    IriRef::new_unchecked("http://www.w3.org/2001/XMLSchema#string".into()),
+                ),
+            )
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap_err();
+        match e {
+            Error::BadExtraOption { predicate } => {
+                assert_eq!(&*predicate, "https://w3id.org/security#nonce")
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        let e = <SignOptions<'_, '_>>::new()
+            .extra_option(
+                Iri::new_unchecked("https://example.com/ns#purpose"),
+                SimpleTerm::Variable(VarName::new_unchecked("x".into())),
+            )
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap_err();
+        match e {
+            Error::BadExtraOption { predicate } => {
+                assert_eq!(&*predicate, "https://example.com/ns#purpose")
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sign", not(feature = "thread-rng")))]
+mod no_rng_tests {
+    use crate::util::test::{parse_nq, rsa_private_key};
+
+    use super::*;
+
+    #[test]
+    fn no_rng() {
+        let dataset =
+            parse_nq(r#"_:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" ."#);
+        let key = rsa_private_key();
+        let creator = Iri::new_unchecked("https://example.com/#me");
+
+        let mut options = <SignOptions<'_, '_>>::new();
+        options.created("2024-01-01T00:00:00Z");
+        let e = options
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap_err();
+        assert!(matches!(e, Error::NoRng), "{:?}", e);
+
+        // The nonce doesn't need an RNG, but the blinding of the RSA operation does.
+        options.nonce(Some("deadbeef12345678"));
+        let e = options
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap_err();
+        assert!(matches!(e, Error::NoRng), "{:?}", e);
+
+        options.deterministic([0; 32]);
+        options
+            .sign_rsa_signature_2017(&dataset, &key, creator)
+            .unwrap();
+    }
 }
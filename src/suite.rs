@@ -0,0 +1,164 @@
+#[cfg(feature = "sign")]
+use rand_core::{CryptoRng, RngCore};
+use rsa::traits::PublicKeyParts;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey, RsaPublicKey};
+use sha2::digest::{FixedOutputReset, Output, Update};
+use sha2::{Digest, Sha256};
+use sophia_iri::Iri;
+
+#[cfg(feature = "sign")]
+use crate::error::KeyError;
+use crate::SignatureType;
+
+/// A signature suite of Linked Data Signatures.
+///
+/// The suites share the hash-chain structure of the Create Verify Hash Algorithm and differ in the
+/// message digest and the signature algorithms.
+pub trait SignatureSuite {
+    /// The type of the signature suite.
+    const TYPE: SignatureType;
+
+    /// The message digest algorithm used by the Create Verify Hash Algorithm.
+    type Digest: Digest + Update + FixedOutputReset;
+    /// The private key type of the signature algorithm.
+    type SigningKey: ?Sized;
+    /// The public key type of the signature algorithm.
+    type VerifyingKey: ?Sized;
+    /// The error raised when a signature didn't verify.
+    type VerificationError;
+
+    /// Returns the IRI identifying the signature suite.
+    fn type_iri() -> Iri<&'static str>;
+
+    /// Signs the output of the Create Verify Hash Algorithm.
+    #[cfg(feature = "sign")]
+    fn sign<R>(key: &Self::SigningKey, rng: &mut R, hash: &Output<Self::Digest>) -> Vec<u8>
+    where
+        R: RngCore + CryptoRng;
+
+    /// Checks that the `key` can sign the output of the Create Verify Hash Algorithm.
+    ///
+    /// The signature operations fail early with the error instead of panicking in
+    /// [`sign`](Self::sign). Defaults to accepting any key.
+    #[cfg(feature = "sign")]
+    fn check_signing_key(key: &Self::SigningKey) -> Result<(), KeyError> {
+        let _ = key;
+        Ok(())
+    }
+
+    /// Returns the length in bytes that every signature by the `key` has, if the signature
+    /// algorithm has fixed-length signatures.
+    ///
+    /// The verification fails early with [`Error::SignatureLengthMismatch`] if a signature has a
+    /// different length. Defaults to `None`, which skips the check.
+    ///
+    /// [`Error::SignatureLengthMismatch`]: crate::verify::Error::SignatureLengthMismatch
+    fn signature_len(key: &Self::VerifyingKey) -> Option<usize> {
+        let _ = key;
+        None
+    }
+
+    /// Verifies the `signature` for the output of the Create Verify Hash Algorithm.
+    fn verify(
+        key: &Self::VerifyingKey,
+        hash: &Output<Self::Digest>,
+        signature: &[u8],
+    ) -> Result<(), Self::VerificationError>;
+}
+
+/// The `RsaSignature2017` signature suite.
+#[derive(Debug)]
+pub enum RsaSignature2017 {}
+
+impl SignatureSuite for RsaSignature2017 {
+    const TYPE: SignatureType = SignatureType::RsaSignature2017;
+
+    type Digest = Sha256;
+    type SigningKey = RsaPrivateKey;
+    type VerifyingKey = RsaPublicKey;
+    type VerificationError = rsa::Error;
+
+    fn type_iri() -> Iri<&'static str> {
+        Iri::new_unchecked_const("https://w3id.org/security#RsaSignature2017")
+    }
+
+    #[cfg(feature = "sign")]
+    fn sign<R>(key: &RsaPrivateKey, rng: &mut R, hash: &Output<Sha256>) -> Vec<u8>
+    where
+        R: RngCore + CryptoRng,
+    {
+        key.sign_with_rng(rng, Pkcs1v15Sign::new::<Sha256>(), hash)
+            .unwrap()
+    }
+
+    /// The modulus must be long enough to fit the DigestInfo of SHA-256 along with the minimum
+    /// PKCS#1 v1.5 padding.
+    #[cfg(feature = "sign")]
+    fn check_signing_key(key: &RsaPrivateKey) -> Result<(), KeyError> {
+        // The `0x00 0x01 PS 0x00` framing, where `PS` is at least 8 bytes long.
+        const MIN_PADDING_LEN: usize = 11;
+
+        let scheme = Pkcs1v15Sign::new::<Sha256>();
+        let min_len = scheme.prefix.len() + <Sha256 as Digest>::output_size() + MIN_PADDING_LEN;
+        if key.size() < min_len {
+            return Err(KeyError::ModulusTooSmall {
+                bits: key.n().bits(),
+                minimum: 8 * (min_len - 1) + 1,
+            });
+        }
+        Ok(())
+    }
+
+    /// PKCS#1 v1.5 signatures are exactly as long as the modulus.
+    fn signature_len(key: &RsaPublicKey) -> Option<usize> {
+        Some(key.size())
+    }
+
+    fn verify(
+        key: &RsaPublicKey,
+        hash: &Output<Sha256>,
+        signature: &[u8],
+    ) -> Result<(), rsa::Error> {
+        key.verify(Pkcs1v15Sign::new::<Sha256>(), hash, signature)
+    }
+}
+
+/// The `RsaSignature2017` suite with the digest algorithm `H` in place of SHA-256, for verifying
+/// legacy documents only.
+///
+/// The type is private so that it can never be used to sign.
+#[cfg(feature = "insecure-legacy-digest")]
+pub(crate) struct RsaSignature2017Legacy<H>(core::marker::PhantomData<fn() -> H>);
+
+#[cfg(feature = "insecure-legacy-digest")]
+impl<H> SignatureSuite for RsaSignature2017Legacy<H>
+where
+    H: Digest + Update + FixedOutputReset + sha2::digest::const_oid::AssociatedOid,
+{
+    const TYPE: SignatureType = SignatureType::RsaSignature2017;
+
+    type Digest = H;
+    type SigningKey = RsaPrivateKey;
+    type VerifyingKey = RsaPublicKey;
+    type VerificationError = rsa::Error;
+
+    fn type_iri() -> Iri<&'static str> {
+        RsaSignature2017::type_iri()
+    }
+
+    #[cfg(feature = "sign")]
+    fn sign<R>(_: &RsaPrivateKey, _: &mut R, _: &Output<H>) -> Vec<u8>
+    where
+        R: RngCore + CryptoRng,
+    {
+        unreachable!("legacy digests are only used for verification")
+    }
+
+    fn signature_len(key: &RsaPublicKey) -> Option<usize> {
+        RsaSignature2017::signature_len(key)
+    }
+
+    fn verify(key: &RsaPublicKey, hash: &Output<H>, signature: &[u8]) -> Result<(), rsa::Error> {
+        key.verify(Pkcs1v15Sign::new::<H>(), hash, signature)
+    }
+}
@@ -0,0 +1,42 @@
+//! Utilities for testing code that works with RDF datasets, e.g., the integration tests of crates
+//! depending on this library.
+//!
+//! This module is only available with the `testing` feature, which isn't meant to be enabled
+//! outside of `[dev-dependencies]`.
+
+use sophia_api::dataset::{CollectibleDataset, SetDataset};
+use sophia_api::parser::QuadParser;
+use sophia_inmem::dataset::LightDataset;
+use sophia_turtle::parser::nq::NQuadsParser;
+
+/// Parses an N-Quads document into a dataset.
+///
+/// # Panics
+///
+/// Panics if the `input` is not a valid N-Quads document.
+pub fn parse_nq(input: &str) -> LightDataset {
+    LightDataset::from_quad_source(NQuadsParser {}.parse(input.as_bytes())).unwrap()
+}
+
+/// Asserts that two datasets are isomorphic, i.e., equal up to the renaming of the blank nodes.
+///
+/// The datasets are compared in their canonical N-Quads forms, which are shown on failure.
+///
+/// # Panics
+///
+/// Panics if the datasets are not isomorphic or if either dataset fails to be canonicalized.
+#[track_caller]
+pub fn assert_eq_dataset<L, R>(left: &L, right: &R)
+where
+    L: SetDataset,
+    R: SetDataset,
+{
+    assert_eq!(canonicalize(left), canonicalize(right));
+}
+
+#[track_caller]
+fn canonicalize<D: SetDataset>(dataset: &D) -> String {
+    let mut ret = Vec::new();
+    sophia_c14n::rdfc10::normalize(dataset, &mut ret).unwrap();
+    String::from_utf8_lossy(&ret).into_owned()
+}
@@ -1,24 +1,95 @@
+/// Emits a `tracing` event at the given level if the `tracing` feature is enabled, or does nothing
+/// otherwise.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)*);
+    };
+}
+
 #[cfg(test)]
 #[macro_use]
 pub mod test;
 
 mod crypto;
 
-pub use self::crypto::{gen_nonce, DigestWrite, NeverRng};
+pub use self::crypto::DigestWrite;
+#[cfg(feature = "sign")]
+pub use self::crypto::{gen_nonce, NeverRng};
 
+#[cfg(feature = "sign")]
 use core::num::NonZeroU8;
+#[cfg(any(feature = "sign", feature = "verify"))]
 use std::time::SystemTime;
 
-use time::format_description::well_known::iso8601::{self, Iso8601};
+#[cfg(feature = "sign")]
+use time::format_description::well_known::iso8601;
+#[cfg(any(feature = "sign", feature = "verify"))]
+use time::format_description::well_known::Iso8601;
+#[cfg(any(feature = "sign", feature = "verify"))]
 use time::OffsetDateTime;
 
-pub fn format_iso8601_time(time: SystemTime) -> String {
+/// Returns the current time, used when no time is given explicitly, or `None` if there is no clock.
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub fn now() -> Option<SystemTime> {
+    Some(SystemTime::now())
+}
+
+/// `SystemTime::now` panics on `wasm32-unknown-unknown`, so the callers fail with an error instead.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub fn now() -> Option<SystemTime> {
+    None
+}
+
+/// Decodes a `signatureValue`, which should be in the standard base64 encoding, but is also
+/// accepted in the base64url encoding (with or without padding) for interoperability.
+#[cfg(any(feature = "json-ld", feature = "serde"))]
+pub fn decode_signature_value(value: &[u8]) -> Option<Vec<u8>> {
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE, URL_SAFE_NO_PAD};
+    use base64::Engine as _;
+
+    STANDARD
+        .decode(value)
+        .or_else(|_| URL_SAFE.decode(value))
+        .or_else(|_| URL_SAFE_NO_PAD.decode(value))
+        .ok()
+}
+
+/// Formats the wrapped bytes as a quoted standard base64 string in `Debug` output.
+pub struct Base64Debug<'a>(pub &'a [u8]);
+
+impl<'a> core::fmt::Debug for Base64Debug<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "\"{}\"",
+            base64::display::Base64Display::new(self.0, &base64::engine::general_purpose::STANDARD)
+        )
+    }
+}
+
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub fn parse_iso8601_datetime(datetime: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(datetime, &Iso8601::DEFAULT).ok()
+}
+
+/// Formats the `time` with `decimal_digits` digits of fractional seconds (at most 9).
+#[cfg(feature = "sign")]
+pub fn format_iso8601_time(time: SystemTime, decimal_digits: u8) -> String {
+    format_iso8601_datetime(time.into(), decimal_digits)
+}
+
+/// Formats the `datetime` with `decimal_digits` digits of fractional seconds (at most 9), which
+/// are truncated rather than rounded.
+#[cfg(feature = "sign")]
+pub fn format_iso8601_datetime(datetime: OffsetDateTime, decimal_digits: u8) -> String {
     const FORMAT: Iso8601<
         {
             iso8601::Config::DEFAULT
-                // Default precision of 9 digits seems to be too much.
                 .set_time_precision(iso8601::TimePrecision::Second {
-                    decimal_digits: NonZeroU8::new(3),
+                    decimal_digits: NonZeroU8::new(9),
                 })
                 .encode()
         },
@@ -26,5 +97,16 @@ pub fn format_iso8601_time(time: SystemTime) -> String {
 
     // XXX: There appears to be no way to avoid the allocation of the `String`,
     // <https://github.com/time-rs/time/issues/375>
-    OffsetDateTime::from(time).format(&FORMAT).unwrap()
+    let mut ret = datetime.format(&FORMAT).unwrap();
+    // The `time` crate can't take the precision at runtime, so format with the full precision and
+    // cut the excess digits off.
+    let decimal_digits = usize::from(decimal_digits.min(9));
+    let point = ret.find('.').unwrap();
+    let start = if decimal_digits == 0 {
+        point
+    } else {
+        point + 1 + decimal_digits
+    };
+    ret.replace_range(start..point + 10, "");
+    ret
 }
@@ -1,15 +1,38 @@
 use std::io::{self, Write};
 
+#[cfg(feature = "sign")]
 use base64::Engine as _;
+#[cfg(feature = "sign")]
 use rand_core::{CryptoRng, RngCore};
 use sha2::digest::Update;
 
+/// An adapter that implements [`io::Write`] for a digest, feeding the written bytes to it.
+///
+/// This is useful for hashing the output of a function that writes to an `io::Write`, like
+/// [`sophia_c14n::rdfc10::normalize`], without buffering it.
+///
+/// ## Example
+///
+/// ```
+/// use rsa_signature_2017::DigestWrite;
+/// use sha2::{Digest, Sha256};
+/// use sophia_inmem::dataset::LightDataset;
+///
+/// let dataset = LightDataset::new();
+/// let mut hasher = Sha256::new();
+/// sophia_c14n::rdfc10::normalize(&dataset, DigestWrite::new(&mut hasher)).unwrap();
+/// let digest = hasher.finalize();
+/// # assert_eq!(digest, Sha256::digest(b""));
+/// ```
+#[derive(Debug)]
 pub struct DigestWrite<'a, D>(&'a mut D);
 
+#[cfg(feature = "sign")]
 #[derive(Debug)]
 pub enum NeverRng {}
 
 impl<'a, D: Update> DigestWrite<'a, D> {
+    /// Creates a new `DigestWrite` that feeds the written bytes to the `digest`.
     pub fn new(digest: &'a mut D) -> Self {
         Self(digest)
     }
@@ -26,6 +49,7 @@ impl<'a, D: Update> Write for DigestWrite<'a, D> {
     }
 }
 
+#[cfg(feature = "sign")]
 impl RngCore for NeverRng {
     fn next_u32(&mut self) -> u32 {
         match *self {}
@@ -44,9 +68,11 @@ impl RngCore for NeverRng {
     }
 }
 
+#[cfg(feature = "sign")]
 impl CryptoRng for NeverRng {}
 
-pub fn gen_nonce<R: RngCore + CryptoRng>(rng: &mut R) -> String {
+#[cfg(feature = "sign")]
+pub fn gen_nonce<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> String {
     pub const ENCODED_LEN: usize = 20;
     let mut rand = [0_u8; ENCODED_LEN * 3 / 4];
     rng.fill_bytes(&mut rand);
@@ -1,24 +1,62 @@
 macro_rules! assert_eq_dataset {
     ($left:expr, $right:expr) => {
-        match (&$left, &$right) {
-            (left, right) => {
-                let mut left_c14n = Vec::new();
-                sophia_c14n::rdfc10::normalize(left, &mut left_c14n).unwrap();
-                let left_c14n = String::from_utf8_lossy(&left_c14n);
-                let mut right_c14n = Vec::new();
-                sophia_c14n::rdfc10::normalize(right, &mut right_c14n).unwrap();
-                let right_c14n = String::from_utf8_lossy(&right_c14n);
-                assert_eq!(left_c14n, right_c14n);
-            }
-        }
+        $crate::testing::assert_eq_dataset(&$left, &$right)
     };
 }
 
-use sophia_api::dataset::CollectibleDataset;
-use sophia_api::parser::QuadParser;
-use sophia_inmem::dataset::LightDataset;
-use sophia_turtle::parser::nq::NQuadsParser;
+pub use crate::testing::parse_nq;
 
-pub fn parse_nq(input: &str) -> LightDataset {
-    LightDataset::from_quad_source(NQuadsParser {}.parse(input.as_bytes())).unwrap()
+/// A minimal ActivityStreams note to be signed in tests.
+#[cfg(all(feature = "thread-rng", feature = "verify"))]
+pub const DATASET: &str = r#"
+    _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .
+    _:b0 <https://www.w3.org/ns/activitystreams#content> "Hello, world!" .
+"#;
+
+/// The `creator` of the signatures made in tests.
+#[cfg(all(feature = "thread-rng", feature = "verify"))]
+pub const CREATOR: &str = "https://example.com/#me";
+
+/// Returns a (weak) RSA private key for testing purposes.
+pub fn rsa_private_key() -> rsa::RsaPrivateKey {
+    use rsa::{BigUint, RsaPrivateKey};
+
+    const N: &[u8] = b"\x00\xa7\x73\xe0\x0f\x71\x95\xeb\xd4\xcb\x36\x25\x2f\x4b\x25\xed\x6e\x3f\x37\xdb\x8a\x3d\x2b\x61\x67\xf2\xea\xe4\x98\x5b\xdb\x56\x5b\x1a\x2c\xa2\xaf\xf7\x65\xcf\xdf\xb5\xa1\x61\x76\x63\x76\x9f\xd2\x64\x18\x05\xd2\x32\x7e\x52\x51\x31\xb2\x8d\x8b\x5a\xa4\x01\x29";
+    const D: &[u8] = b"\x00\x8c\x32\x92\x07\x9c\x1b\xdf\x65\x3b\xf6\x4b\x4f\xbb\x65\x37\xd2\xb4\x0f\x3a\x3a\x15\x58\xba\xa6\xe3\x55\x12\xab\x15\x4d\x20\x90\xae\x53\x71\x9a\xc6\x8b\xd5\xd1\xaa\x94\x63\xbb\x9e\xe2\x72\x90\x10\xb4\x14\xf7\x86\xc4\x03\xaa\x6f\x28\x7d\x1e\x7b\xc7\xa4\x61";
+    const P: &[u8] = b"\x00\xde\x79\xa1\x11\xd2\xac\x22\x86\xc7\xdc\xb2\x03\x01\x05\xcc\x73\x9e\x17\x3c\xef\x9d\x31\x13\x39\x9f\x81\xe6\x68\x85\xdc\xe7\x0d";
+    const Q: &[u8] = b"\x00\xc0\xaf\xa8\x21\x8b\x40\xca\x59\xd5\x00\xd0\x55\x68\xf6\x7f\x35\xd0\x29\xfb\xb6\xb3\x38\x33\xe4\x81\xd6\x6b\x5d\x93\x6b\xfb\x8d";
+
+    RsaPrivateKey::from_components(
+        BigUint::from_bytes_be(N),
+        BigUint::from(65537u64),
+        BigUint::from_bytes_be(D),
+        vec![BigUint::from_bytes_be(P), BigUint::from_bytes_be(Q)],
+    )
+    .unwrap()
+}
+
+/// Signs the `dataset` with the `options` using [`rsa_private_key`] and [`CREATOR`], and checks that
+/// the signature verifies against the options it carries.
+#[cfg(all(feature = "thread-rng", feature = "verify"))]
+pub fn sign_and_verify<'sig, D, R>(
+    options: &mut crate::SignOptions<'sig, '_, R>,
+    dataset: &D,
+) -> crate::Signature<'sig>
+where
+    D: sophia_api::dataset::SetDataset,
+    D::Error: core::fmt::Debug,
+    R: rand_core::RngCore + rand_core::CryptoRng,
+{
+    let key = rsa_private_key();
+    let signature = options
+        .sign_rsa_signature_2017(dataset, &key, sophia_iri::Iri::new_unchecked(CREATOR))
+        .unwrap();
+    crate::verify_rsa_signature_2017(
+        dataset,
+        &crate::common::SignatureOptions::from(&signature).to_dataset(),
+        key.as_ref(),
+        &signature.signature_value,
+    )
+    .unwrap();
+    signature
 }
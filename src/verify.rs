@@ -1,13 +1,83 @@
-use rsa::{Pkcs1v15Sign, RsaPublicKey};
-use sha2::Sha256;
-use sophia_api::dataset::SetDataset;
+use core::fmt::{self, Debug, Formatter};
+use core::time::Duration;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::SystemTime;
 
-use crate::common::create_verify_hash;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+#[cfg(feature = "insecure-legacy-digest")]
+use sha2::digest::const_oid::AssociatedOid;
+use sha2::digest::Output;
+#[cfg(feature = "insecure-legacy-digest")]
+use sha2::digest::{FixedOutputReset, Update};
+use sha2::Digest;
+use sophia_api::dataset::{Dataset, MutableDataset, SetDataset};
+use sophia_api::quad::Quad;
+use sophia_api::term::{matcher, BnodeId, SimpleTerm, Term, TermKind};
+use sophia_api::MownStr;
+use sophia_inmem::dataset::LightDataset;
+use sophia_iri::{Iri, IriRef};
+
+#[cfg(feature = "json-ld")]
+use crate::common::create_verify_hash_prehashed;
+use crate::common::{check_quad_count, consts, create_verify_hash_with, GraphScope};
 use crate::error::DatasetError;
+#[cfg(feature = "insecure-legacy-digest")]
+use crate::suite::RsaSignature2017Legacy;
+use crate::suite::{RsaSignature2017, SignatureSuite};
+use crate::util;
+
+/// Options for verifying a signature.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct VerifyOptions<'a> {
+    /// The expected `rdf:type` of the primary subject of the document.
+    pub expected_type: Option<IriRef<&'a str>>,
+    /// The maximum number of quads allowed in each of the document and options datasets.
+    pub max_quads: Option<usize>,
+    /// The graphs of the document covered by the signature.
+    pub graph_scope: GraphScope,
+    /// The maximum age of the signature.
+    pub max_age: Option<Duration>,
+    /// The time to check the age of the signature against, defaulting to the current time.
+    pub now: Option<SystemTime>,
+}
+
+/// A detailed result of a signature verification, returned by
+/// [`VerifyOptions::verify_rsa_signature_2017_report`].
+///
+/// Unlike the `Result` of the other verification methods, this reports the cryptographic and the
+/// temporal validity separately, so that the caller can decide its own policy, e.g., accepting a
+/// slightly expired signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VerificationReport {
+    /// Whether the signature verified cryptographically.
+    pub crypto_ok: bool,
+    /// Whether the signature was created within the [`max_age`](VerifyOptions::max_age), or `None`
+    /// if the `max_age` is not set.
+    pub within_validity: Option<bool>,
+    /// The `creator` (or `verificationMethod`) IRI of the signature options.
+    pub creator: Option<Box<str>>,
+    /// The `created` datetime of the signature options.
+    pub created: Option<Box<str>>,
+}
+
+/// A verifier of signatures with the signature suite `S`, which reuses its internal buffers across
+/// verifications.
+///
+/// This is useful when verifying many signatures in a row. For a one-off verification, use
+/// [`VerifyOptions::verify`] or [`verify_rsa_signature_2017`] instead, which are thin wrappers
+/// over a fresh `Verifier`.
+pub struct Verifier<'a, S: SignatureSuite = RsaSignature2017> {
+    options: VerifyOptions<'a>,
+    hasher: S::Digest,
+    scratch: Vec<u8>,
+}
 
 /// Error while verifying a signature.
-#[derive(Debug, thiserror::Error)]
-pub enum Error<DE, OE = DE> {
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error<DE, OE = DE, VE = rsa::Error> {
     /// The input dataset raised an error.
     #[error("Error from dataset: {0}")]
     Dataset(DatasetError<DE>),
@@ -16,23 +86,852 @@ pub enum Error<DE, OE = DE> {
     Options(DatasetError<OE>),
     /// The signature didn't verify.
     #[error("Signature didn't verify: {0}")]
-    Verification(rsa::Error),
+    Verification(VE),
+    /// The primary subject of the document didn't have the expected type.
+    #[error("Unexpected document type: {found:?}")]
+    UnexpectedDocumentType {
+        /// The types of the primary subjects of the document.
+        found: Vec<Box<str>>,
+    },
+    /// The signature wasn't created within the maximum age.
+    #[error("Signature is out of its validity period (created: {created:?})")]
+    OutOfValidity {
+        /// The `created` datetime of the signature options.
+        created: Option<Box<str>>,
+    },
+    /// The signature value didn't have the length that the key's signatures always have.
+    #[error("Signature value is {actual} bytes long, but the key signs {expected} bytes")]
+    SignatureLengthMismatch {
+        /// The length of the signatures of the key in bytes.
+        expected: usize,
+        /// The length of the signature value in bytes.
+        actual: usize,
+    },
+    /// The age of the signature was to be checked but no time was set with
+    /// [`VerifyOptions::now`], and the current time was unavailable, i.e., the target was
+    /// WebAssembly with the `wasm` feature.
+    #[error("The current time is unavailable; set it with `VerifyOptions::now`")]
+    NoClock,
+}
+
+impl<DE, VE> From<DatasetError<DE>> for Error<DE, DE, VE> {
+    /// Converts a [`DatasetError`] into an [`Error::Dataset`].
+    fn from(e: DatasetError<DE>) -> Self {
+        Error::Dataset(e)
+    }
+}
+
+impl<'a> VerifyOptions<'a> {
+    /// Creates a new `VerifyOptions` with a default set of options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the expected `rdf:type` of the document.
+    ///
+    /// If set, the verification fails unless a primary subject of the document, i.e., a subject in
+    /// the default graph that doesn't appear as an object of any quad, has the type. The check is
+    /// done after the cryptographic verification.
+    pub fn expected_type(
+        &mut self,
+        expected_type: impl Into<Option<IriRef<&'a str>>>,
+    ) -> &mut Self {
+        self.expected_type = expected_type.into();
+        self
+    }
+
+    /// Sets the maximum number of quads allowed in each of the document and options datasets.
+    ///
+    /// If set, the verification fails with [`DatasetError::TooManyQuads`] before canonicalizing a
+    /// dataset with more quads than the limit. This is a cheap guard against untrusted documents
+    /// that would take a lot of work to canonicalize.
+    pub fn max_quads(&mut self, max_quads: impl Into<Option<usize>>) -> &mut Self {
+        self.max_quads = max_quads.into();
+        self
+    }
+
+    /// Sets the graphs of the document covered by the signature.
+    ///
+    /// Defaults to [`GraphScope::All`]. Strict callers may set [`GraphScope::Default`] to ignore
+    /// the named graphs of the document like some other implementations do.
+    pub fn graph_scope(&mut self, graph_scope: GraphScope) -> &mut Self {
+        self.graph_scope = graph_scope;
+        self
+    }
+
+    /// Sets the maximum age of the signature.
+    ///
+    /// If set, the verification fails with [`Error::OutOfValidity`] unless the `created` datetime of
+    /// the signature options is within the age before the [`now`](Self::now) time. The check is
+    /// done after the cryptographic verification. Use
+    /// [`verify_rsa_signature_2017_report`](Self::verify_rsa_signature_2017_report) to check the
+    /// validity without failing.
+    pub fn max_age(&mut self, max_age: impl Into<Option<Duration>>) -> &mut Self {
+        self.max_age = max_age.into();
+        self
+    }
+
+    /// Sets the time to check the age of the signature against.
+    ///
+    /// By default, the current time is used. That is unavailable on WebAssembly targets with the
+    /// `wasm` feature, where checking the [`max_age`](Self::max_age) without this fails with
+    /// [`Error::NoClock`].
+    pub fn now(&mut self, now: impl Into<Option<SystemTime>>) -> &mut Self {
+        self.now = now.into();
+        self
+    }
+
+    /// Verifies the `signature` as an `RsaSignature2017` for the given `dataset` and the signature
+    /// `options`.
+    ///
+    /// The `options` dataset is hashed as is, so it must not contain the `rdf:type` or
+    /// `signatureValue` properties of the signature node, which the signer removes before hashing
+    /// (the JSON-LD parser removes the `type` and `signatureValue` entries likewise). Such triples
+    /// make the verification fail. Use
+    /// [`verify_with_options_dataset`](Self::verify_with_options_dataset) if the `options` may
+    /// contain them.
+    ///
+    /// See also [`verify_rsa_signature_2017`] function, which is a shorthand for this method.
+    pub fn verify_rsa_signature_2017<D, O, K>(
+        &self,
+        dataset: &D,
+        options: &O,
+        key: &K,
+        signature: &[u8],
+    ) -> Result<(), Error<D::Error, O::Error>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+        K: AsRsaPublicKey + ?Sized,
+    {
+        let key = key.as_rsa_public_key();
+        self.verify::<RsaSignature2017, D, O>(dataset, options, key, signature)
+    }
+
+    /// Verifies the `signature` as an `RsaSignature2017` like
+    /// [`verify_rsa_signature_2017`](Self::verify_rsa_signature_2017), but accepts it if any of the
+    /// candidate `keys` matches, e.g., during a key rotation, and returns the index of the first
+    /// matching key.
+    ///
+    /// The document is canonicalized only once for all the keys. Errors other than a key not
+    /// matching the signature, e.g., of the datasets, are returned immediately without trying the
+    /// rest of the keys. If none of the keys matches, fails with [`Error::Verification`].
+    ///
+    /// See also [`verify_rsa_signature_2017_any`] function, which is a shorthand for this method.
+    pub fn verify_rsa_signature_2017_any<D, O, K>(
+        &self,
+        dataset: &D,
+        options: &O,
+        keys: &[K],
+        signature: &[u8],
+    ) -> Result<usize, Error<D::Error, O::Error>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+        K: AsRsaPublicKey,
+    {
+        let mut verifier = Verifier::<RsaSignature2017>::new(self.clone());
+        let to_be_verified = verifier.create_verify_hash(dataset, options)?;
+        // A key of a different size than the signature simply doesn't match it.
+        let index = keys
+            .iter()
+            .map(AsRsaPublicKey::as_rsa_public_key)
+            .position(|key| {
+                check_signature_len::<RsaSignature2017, (), ()>(key, signature).is_ok()
+                    && RsaSignature2017::verify(key, &to_be_verified, signature).is_ok()
+            })
+            .ok_or(Error::Verification(rsa::Error::Verification))?;
+        verifier.check_claims(dataset, options)?;
+        Ok(index)
+    }
+
+    /// Verifies the `signature` like
+    /// [`verify_rsa_signature_2017`](Self::verify_rsa_signature_2017), but with the digest
+    /// algorithm `H` in place of SHA-256 throughout the Create Verify Hash Algorithm and the
+    /// PKCS#1 v1.5 signature.
+    ///
+    /// **This is insecure.** It exists only to check historical documents labeled as
+    /// `RsaSignature2017` that were actually produced with a weaker digest algorithm, typically
+    /// SHA-1 (pass `sha1::Sha1` from the `sha1` crate as `H`). A successful verification says
+    /// little about the authenticity of the document, since a SHA-1 collision lets an attacker
+    /// substitute a different document. Never accept such signatures for new documents. The crate
+    /// never signs with anything but SHA-256.
+    ///
+    /// See also [`verify_rsa_signature_2017_legacy`] function, which is a shorthand for this
+    /// method.
+    #[cfg(feature = "insecure-legacy-digest")]
+    pub fn verify_rsa_signature_2017_legacy<H, D, O, K>(
+        &self,
+        dataset: &D,
+        options: &O,
+        key: &K,
+        signature: &[u8],
+    ) -> Result<(), Error<D::Error, O::Error>>
+    where
+        H: Digest + Update + FixedOutputReset + AssociatedOid,
+        D: SetDataset,
+        O: SetDataset,
+        K: AsRsaPublicKey + ?Sized,
+    {
+        let key = key.as_rsa_public_key();
+        self.verify::<RsaSignature2017Legacy<H>, D, O>(dataset, options, key, signature)
+    }
+
+    /// Verifies the `signature` for the given `dataset` and the signature `options` with the
+    /// signature suite `S`.
+    #[allow(clippy::type_complexity)]
+    pub fn verify<S, D, O>(
+        &self,
+        dataset: &D,
+        options: &O,
+        key: &S::VerifyingKey,
+        signature: &[u8],
+    ) -> Result<(), Error<D::Error, O::Error, S::VerificationError>>
+    where
+        S: SignatureSuite,
+        D: SetDataset,
+        O: SetDataset,
+    {
+        Verifier::<S>::new(self.clone()).verify(dataset, options, key, signature)
+    }
+
+    /// Verifies the `signature` as an `RsaSignature2017` like
+    /// [`verify_rsa_signature_2017`](Self::verify_rsa_signature_2017), but returns a
+    /// [`VerificationReport`] instead of failing when the signature doesn't verify
+    /// cryptographically or is out of its validity period.
+    ///
+    /// See also [`verify_rsa_signature_2017_report`] function, which is a shorthand for this method.
+    pub fn verify_rsa_signature_2017_report<D, O, K>(
+        &self,
+        dataset: &D,
+        options: &O,
+        key: &K,
+        signature: &[u8],
+    ) -> Result<VerificationReport, Error<D::Error, O::Error>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+        K: AsRsaPublicKey + ?Sized,
+    {
+        let key = key.as_rsa_public_key();
+        Verifier::<RsaSignature2017>::new(self.clone())
+            .verify_report(dataset, options, key, signature)
+    }
+
+    /// Verifies each of the `items`, a tuple of a dataset, signature options and a signature, and
+    /// returns the results in the same order as the `items`.
+    ///
+    /// See also [`verify_batch`] function, which is a shorthand for this method.
+    #[allow(clippy::type_complexity)]
+    pub fn verify_batch<'b, D, O, I, K>(
+        &self,
+        items: I,
+        key: &K,
+    ) -> Vec<Result<(), Error<D::Error, O::Error>>>
+    where
+        I: IntoIterator<Item = (&'b D, &'b O, &'b [u8])>,
+        D: SetDataset + 'b,
+        O: SetDataset + 'b,
+        K: AsRsaPublicKey + ?Sized,
+    {
+        let key = key.as_rsa_public_key();
+        let mut verifier = Verifier::<RsaSignature2017>::new(self.clone());
+        items
+            .into_iter()
+            .map(|(dataset, options, signature)| verifier.verify(dataset, options, key, signature))
+            .collect()
+    }
+
+    /// Verifies each of the `items` like [`verify_batch`](Self::verify_batch), but stops at the
+    /// first failure and returns its index along with the error.
+    ///
+    /// See also [`verify_batch_all`] function, which is a shorthand for this method.
+    #[allow(clippy::type_complexity)]
+    pub fn verify_batch_all<'b, D, O, I, K>(
+        &self,
+        items: I,
+        key: &K,
+    ) -> Result<(), (usize, Error<D::Error, O::Error>)>
+    where
+        I: IntoIterator<Item = (&'b D, &'b O, &'b [u8])>,
+        D: SetDataset + 'b,
+        O: SetDataset + 'b,
+        K: AsRsaPublicKey + ?Sized,
+    {
+        let key = key.as_rsa_public_key();
+        let mut verifier = Verifier::<RsaSignature2017>::new(self.clone());
+        items
+            .into_iter()
+            .enumerate()
+            .try_for_each(|(i, (dataset, options, signature))| {
+                verifier
+                    .verify(dataset, options, key, signature)
+                    .map_err(|e| (i, e))
+            })
+    }
+
+    /// Verifies the `signature_value` as an `RsaSignature2017` for the given `document` and an
+    /// `options` dataset describing the signature node.
+    ///
+    /// Unlike [`verify_rsa_signature_2017`](Self::verify_rsa_signature_2017), the `options` dataset
+    /// may contain the `rdf:type` and `signatureValue` properties of the signature node, which are
+    /// ignored, and the signature node may be identified by the `id` IRI, which is treated as a
+    /// blank node. This is useful when the datasets are obtained from an RDF serialization other
+    /// than JSON-LD.
+    ///
+    /// See also [`verify_with_options_dataset`] function, which is a shorthand for this method.
+    pub fn verify_with_options_dataset<D, O, K>(
+        &self,
+        document: &D,
+        options: &O,
+        id: Option<&str>,
+        signature_value: &[u8],
+        key: &K,
+    ) -> Result<(), Error<D::Error, O::Error>>
+    where
+        D: SetDataset,
+        O: Dataset,
+        K: AsRsaPublicKey + ?Sized,
+    {
+        let key = key.as_rsa_public_key();
+        const ID: MownStr<'_> = MownStr::from_str("b0");
+
+        let mut stripped = LightDataset::new();
+        for quad in options.quads() {
+            let ([s, p, o], g) = quad
+                .map_err(|e| Error::Options(DatasetError::Dataset(e)))?
+                .to_spog();
+            if p.eq(Iri::new_unchecked(consts::TYPE))
+                || p.eq(Iri::new_unchecked(consts::SIGNATURE_VALUE))
+            {
+                continue;
+            }
+            // Unwrapping is fine because `LightDataset::insert` returns error only when too many
+            // terms are inserted.
+            if id.is_some() && s.iri().as_ref().map(|iri| iri.as_str()) == id {
+                stripped
+                    .insert(BnodeId::new_unchecked(ID), p, o, g)
+                    .unwrap();
+            } else {
+                stripped.insert(s, p, o, g).unwrap();
+            }
+        }
+
+        self.verify_rsa_signature_2017(document, &stripped, key, signature_value)
+            .map_err(|e| match e {
+                Error::Dataset(e) => Error::Dataset(e),
+                // The options come from the caller or the document, so canonicalizing them may fail.
+                Error::Options(e) => Error::Options(match e {
+                    // `LightDataset` returns error only when inserting quads.
+                    DatasetError::Dataset(_) => unreachable!(),
+                    DatasetError::ToxicGraph(e) => DatasetError::ToxicGraph(e),
+                    DatasetError::Unsupported(e) => DatasetError::Unsupported(e),
+                    DatasetError::TooManyQuads { limit } => DatasetError::TooManyQuads { limit },
+                }),
+                Error::Verification(e) => Error::Verification(e),
+                Error::UnexpectedDocumentType { found } => Error::UnexpectedDocumentType { found },
+                Error::OutOfValidity { created } => Error::OutOfValidity { created },
+                Error::SignatureLengthMismatch { expected, actual } => {
+                    Error::SignatureLengthMismatch { expected, actual }
+                }
+                Error::NoClock => Error::NoClock,
+            })
+    }
+}
+
+impl<'a, S: SignatureSuite> Verifier<'a, S> {
+    /// Creates a new `Verifier` with the given options.
+    pub fn new(options: VerifyOptions<'a>) -> Self {
+        Verifier {
+            options,
+            hasher: Digest::new(),
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Returns the options of the verifier.
+    pub fn options(&self) -> &VerifyOptions<'a> {
+        &self.options
+    }
+
+    /// Verifies the `signature` for the given `dataset` and the signature `options`.
+    #[allow(clippy::type_complexity)]
+    pub fn verify<D, O>(
+        &mut self,
+        dataset: &D,
+        options: &O,
+        key: &S::VerifyingKey,
+        signature: &[u8],
+    ) -> Result<(), Error<D::Error, O::Error, S::VerificationError>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+    {
+        self.verify_crypto(dataset, options, key, signature)?;
+        self.check_claims(dataset, options)
+    }
+
+    /// Verifies the `signature` for the given `dataset` and the signature `options` like
+    /// [`verify`](Self::verify), but returns a [`VerificationReport`] instead of failing when the
+    /// signature doesn't verify cryptographically or is out of its validity period.
+    #[allow(clippy::type_complexity)]
+    pub fn verify_report<D, O>(
+        &mut self,
+        dataset: &D,
+        options: &O,
+        key: &S::VerifyingKey,
+        signature: &[u8],
+    ) -> Result<VerificationReport, Error<D::Error, O::Error, S::VerificationError>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+    {
+        let crypto_ok = match self.verify_crypto(dataset, options, key, signature) {
+            Ok(()) => true,
+            Err(Error::Verification(_) | Error::SignatureLengthMismatch { .. }) => false,
+            Err(e) => return Err(e),
+        };
+
+        if let Some(expected_type) = self.options.expected_type {
+            check_document_type(dataset, expected_type)?;
+        }
+
+        let created = find_created(options)?;
+        let creator = find_creator(options)?;
+        let within_validity = self.is_within_validity(created.as_deref())?;
+
+        Ok(VerificationReport {
+            crypto_ok,
+            within_validity,
+            creator,
+            created,
+        })
+    }
+
+    /// Verifies the `signature` for a document whose hex-encoded hash is `document_hex`, computed
+    /// by [`hash_document_hex`](crate::common::hash_document_hex), and the signature `options`.
+    ///
+    /// The options that depend on the document itself, i.e., `expected_type` and the `max_quads` of
+    /// the document, are not checked.
+    #[cfg(feature = "json-ld")]
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn verify_prehashed<DE, O>(
+        &mut self,
+        document_hex: &[u8],
+        options: &O,
+        key: &S::VerifyingKey,
+        signature: &[u8],
+    ) -> Result<(), Error<DE, O::Error, S::VerificationError>>
+    where
+        O: SetDataset,
+    {
+        check_signature_len::<S, _, _>(key, signature)?;
+        if let Some(limit) = self.options.max_quads {
+            check_quad_count(options, limit).map_err(Error::Options)?;
+        }
+
+        let to_be_verified = create_verify_hash_prehashed(
+            document_hex,
+            options,
+            &mut self.hasher,
+            &mut self.scratch,
+        )
+        .map_err(Error::Options)?;
+        S::verify(key, &to_be_verified, signature).map_err(Error::Verification)?;
+
+        if self.options.max_age.is_some() {
+            let created = find_created(options)?;
+            if self.is_within_validity(created.as_deref())? == Some(false) {
+                return Err(Error::OutOfValidity { created });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn verify_crypto<D, O>(
+        &mut self,
+        dataset: &D,
+        options: &O,
+        key: &S::VerifyingKey,
+        signature: &[u8],
+    ) -> Result<(), Error<D::Error, O::Error, S::VerificationError>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+    {
+        check_signature_len::<S, _, _>(key, signature)?;
+        let to_be_verified = self.create_verify_hash(dataset, options)?;
+        trace_event!(
+            debug,
+            signature_len = signature.len(),
+            "verifying the signature"
+        );
+        let result = S::verify(key, &to_be_verified, signature);
+        trace_event!(debug, ok = result.is_ok(), "verified the signature");
+        result.map_err(Error::Verification)
+    }
+
+    /// Checks the size of the datasets and returns the output of the Create Verify Hash Algorithm.
+    #[allow(clippy::type_complexity)]
+    fn create_verify_hash<D, O>(
+        &mut self,
+        dataset: &D,
+        options: &O,
+    ) -> Result<Output<S::Digest>, Error<D::Error, O::Error, S::VerificationError>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+    {
+        if let Some(limit) = self.options.max_quads {
+            check_quad_count(dataset, limit).map_err(Error::Dataset)?;
+            check_quad_count(options, limit).map_err(Error::Options)?;
+        }
+        // A signature over an empty document is valid but most likely not what the signer meant.
+        if let Ok(true) = crate::common::is_empty_in_scope(dataset, self.options.graph_scope) {
+            trace_event!(warn, "verifying a signature over a document with no quads");
+        }
+
+        create_verify_hash_with(
+            dataset,
+            options,
+            self.options.graph_scope,
+            &mut self.hasher,
+            &mut self.scratch,
+        )
+        .map_err(|e| e.either(Error::Dataset, Error::Options))
+    }
+
+    /// Checks the `expected_type` of the document and the validity period of the signature, which
+    /// follow the cryptographic verification.
+    #[allow(clippy::type_complexity)]
+    fn check_claims<D, O>(
+        &self,
+        dataset: &D,
+        options: &O,
+    ) -> Result<(), Error<D::Error, O::Error, S::VerificationError>>
+    where
+        D: SetDataset,
+        O: SetDataset,
+    {
+        if let Some(expected_type) = self.options.expected_type {
+            check_document_type(dataset, expected_type)?;
+        }
+
+        if self.options.max_age.is_some() {
+            let created = find_created(options)?;
+            if self.is_within_validity(created.as_deref())? == Some(false) {
+                return Err(Error::OutOfValidity { created });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the signature `created` at the datetime is within the `max_age`, or `None`
+    /// if the `max_age` is not set.
+    fn is_within_validity<DE, OE, VE>(
+        &self,
+        created: Option<&str>,
+    ) -> Result<Option<bool>, Error<DE, OE, VE>> {
+        let Some(max_age) = self.options.max_age else {
+            return Ok(None);
+        };
+        let Some(created) = created.and_then(util::parse_iso8601_datetime) else {
+            return Ok(Some(false));
+        };
+        let now = self.options.now.or_else(util::now).ok_or(Error::NoClock)?;
+        Ok(Some(
+            now.duration_since(created.into())
+                .is_ok_and(|age| age <= max_age),
+        ))
+    }
+}
+
+impl<S: SignatureSuite> Default for Verifier<'_, S> {
+    fn default() -> Self {
+        Self::new(VerifyOptions::default())
+    }
+}
+
+impl<S: SignatureSuite> Debug for Verifier<'_, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verifier")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A type that can be viewed as an [`RsaPublicKey`].
+///
+/// The `RsaSignature2017`-specific verification functions accept any implementor of this trait, so
+/// that a key pair or a shared pointer to a key can be passed directly, without extracting the
+/// public key first.
+pub trait AsRsaPublicKey {
+    /// Returns the public key.
+    fn as_rsa_public_key(&self) -> &RsaPublicKey;
+}
+
+impl AsRsaPublicKey for RsaPublicKey {
+    fn as_rsa_public_key(&self) -> &RsaPublicKey {
+        self
+    }
+}
+
+impl AsRsaPublicKey for RsaPrivateKey {
+    fn as_rsa_public_key(&self) -> &RsaPublicKey {
+        self.as_ref()
+    }
+}
+
+impl<D: Digest> AsRsaPublicKey for rsa::pkcs1v15::VerifyingKey<D> {
+    fn as_rsa_public_key(&self) -> &RsaPublicKey {
+        self.as_ref()
+    }
+}
+
+impl<D: Digest> AsRsaPublicKey for rsa::pkcs1v15::SigningKey<D> {
+    fn as_rsa_public_key(&self) -> &RsaPublicKey {
+        AsRef::<RsaPrivateKey>::as_ref(self).as_ref()
+    }
+}
+
+macro_rules! impl_as_rsa_public_key_for_pointer {
+    ($($ty:ty),*) => {$(
+        impl<T: AsRsaPublicKey + ?Sized> AsRsaPublicKey for $ty {
+            fn as_rsa_public_key(&self) -> &RsaPublicKey {
+                (**self).as_rsa_public_key()
+            }
+        }
+    )*};
 }
 
+impl_as_rsa_public_key_for_pointer!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
 /// Verifies the `signature` as an `RsaSignature2017` for the given `dataset` and the signature
 /// `options`.
-pub fn verify_rsa_signature_2017<D, O>(
+///
+/// See also [`VerifyOptions::verify_rsa_signature_2017`].
+pub fn verify_rsa_signature_2017<D, O, K>(
     dataset: &D,
     options: &O,
-    key: &RsaPublicKey,
+    key: &K,
     signature: &[u8],
 ) -> Result<(), Error<D::Error, O::Error>>
 where
     D: SetDataset,
     O: SetDataset,
+    K: AsRsaPublicKey + ?Sized,
 {
-    let to_be_verified = create_verify_hash(dataset, options)
-        .map_err(|e| e.either(Error::Dataset, Error::Options))?;
-    key.verify(Pkcs1v15Sign::new::<Sha256>(), &to_be_verified, signature)
-        .map_err(Error::Verification)
+    VerifyOptions::new().verify_rsa_signature_2017(dataset, options, key, signature)
+}
+
+/// Shorthand for `VerifyOptions::new().verify_rsa_signature_2017_any(…)`.
+///
+/// See also [`VerifyOptions::verify_rsa_signature_2017_any`].
+pub fn verify_rsa_signature_2017_any<D, O, K>(
+    dataset: &D,
+    options: &O,
+    keys: &[K],
+    signature: &[u8],
+) -> Result<usize, Error<D::Error, O::Error>>
+where
+    D: SetDataset,
+    O: SetDataset,
+    K: AsRsaPublicKey,
+{
+    VerifyOptions::new().verify_rsa_signature_2017_any(dataset, options, keys, signature)
+}
+
+/// Shorthand for `VerifyOptions::new().verify_batch(…)`.
+///
+/// See also [`VerifyOptions::verify_batch`].
+#[allow(clippy::type_complexity)]
+pub fn verify_batch<'a, D, O, I, K>(items: I, key: &K) -> Vec<Result<(), Error<D::Error, O::Error>>>
+where
+    I: IntoIterator<Item = (&'a D, &'a O, &'a [u8])>,
+    D: SetDataset + 'a,
+    O: SetDataset + 'a,
+    K: AsRsaPublicKey + ?Sized,
+{
+    VerifyOptions::new().verify_batch(items, key)
+}
+
+/// Shorthand for `VerifyOptions::new().verify_batch_all(…)`.
+///
+/// See also [`VerifyOptions::verify_batch_all`].
+#[allow(clippy::type_complexity)]
+pub fn verify_batch_all<'a, D, O, I, K>(
+    items: I,
+    key: &K,
+) -> Result<(), (usize, Error<D::Error, O::Error>)>
+where
+    I: IntoIterator<Item = (&'a D, &'a O, &'a [u8])>,
+    D: SetDataset + 'a,
+    O: SetDataset + 'a,
+    K: AsRsaPublicKey + ?Sized,
+{
+    VerifyOptions::new().verify_batch_all(items, key)
+}
+
+/// Shorthand for `VerifyOptions::new().verify_with_options_dataset(…)`.
+///
+/// See also [`VerifyOptions::verify_with_options_dataset`].
+pub fn verify_with_options_dataset<D, O, K>(
+    document: &D,
+    options: &O,
+    id: Option<&str>,
+    signature_value: &[u8],
+    key: &K,
+) -> Result<(), Error<D::Error, O::Error>>
+where
+    D: SetDataset,
+    O: Dataset,
+    K: AsRsaPublicKey + ?Sized,
+{
+    VerifyOptions::new().verify_with_options_dataset(document, options, id, signature_value, key)
+}
+
+/// Shorthand for `VerifyOptions::new().verify_rsa_signature_2017_legacy(…)`.
+///
+/// **This is insecure.** See [`VerifyOptions::verify_rsa_signature_2017_legacy`] for the details.
+#[cfg(feature = "insecure-legacy-digest")]
+pub fn verify_rsa_signature_2017_legacy<H, D, O, K>(
+    dataset: &D,
+    options: &O,
+    key: &K,
+    signature: &[u8],
+) -> Result<(), Error<D::Error, O::Error>>
+where
+    H: Digest + Update + FixedOutputReset + AssociatedOid,
+    D: SetDataset,
+    O: SetDataset,
+    K: AsRsaPublicKey + ?Sized,
+{
+    VerifyOptions::new()
+        .verify_rsa_signature_2017_legacy::<H, D, O, K>(dataset, options, key, signature)
+}
+
+/// Shorthand for `VerifyOptions::new().verify_rsa_signature_2017_report(…)`.
+///
+/// See also [`VerifyOptions::verify_rsa_signature_2017_report`].
+pub fn verify_rsa_signature_2017_report<D, O, K>(
+    dataset: &D,
+    options: &O,
+    key: &K,
+    signature: &[u8],
+) -> Result<VerificationReport, Error<D::Error, O::Error>>
+where
+    D: SetDataset,
+    O: SetDataset,
+    K: AsRsaPublicKey + ?Sized,
+{
+    VerifyOptions::new().verify_rsa_signature_2017_report(dataset, options, key, signature)
+}
+
+fn find_created<O, DE, VE>(options: &O) -> Result<Option<Box<str>>, Error<DE, O::Error, VE>>
+where
+    O: Dataset,
+{
+    for quad in options.quads_matching(
+        matcher::Any,
+        [Iri::new_unchecked(consts::CREATED)],
+        TermKind::Literal,
+        [None::<&'static SimpleTerm<'_>>],
+    ) {
+        let o = quad
+            .map_err(|e| Error::Options(DatasetError::Dataset(e)))?
+            .to_o();
+        let created = o.lexical_form().map(Into::into);
+        if created.is_some() {
+            return Ok(created);
+        }
+    }
+    Ok(None)
+}
+
+/// Checks the length of the `signature` before the costly canonicalization, to report a signature
+/// that can never verify more clearly than the signature algorithm would.
+fn check_signature_len<S, DE, OE>(
+    key: &S::VerifyingKey,
+    signature: &[u8],
+) -> Result<(), Error<DE, OE, S::VerificationError>>
+where
+    S: SignatureSuite,
+{
+    match S::signature_len(key) {
+        Some(expected) if expected != signature.len() => Err(Error::SignatureLengthMismatch {
+            expected,
+            actual: signature.len(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn find_creator<O, DE, VE>(options: &O) -> Result<Option<Box<str>>, Error<DE, O::Error, VE>>
+where
+    O: Dataset,
+{
+    for quad in options.quads_matching(
+        matcher::Any,
+        [
+            Iri::new_unchecked(consts::CREATOR),
+            Iri::new_unchecked(consts::VERIFICATION_METHOD),
+        ],
+        TermKind::Iri,
+        [None::<&'static SimpleTerm<'_>>],
+    ) {
+        let o = quad
+            .map_err(|e| Error::Options(DatasetError::Dataset(e)))?
+            .to_o();
+        let creator = o.iri().map(|iri| iri.as_str().into());
+        if creator.is_some() {
+            return Ok(creator);
+        }
+    }
+    Ok(None)
+}
+
+fn check_document_type<D, OE, VE>(
+    dataset: &D,
+    expected_type: IriRef<&str>,
+) -> Result<(), Error<D::Error, OE, VE>>
+where
+    D: Dataset,
+{
+    let mut found = Vec::new();
+
+    for quad in dataset.quads_matching(
+        matcher::Any,
+        [Iri::new_unchecked(consts::TYPE)],
+        TermKind::Iri,
+        [None::<&'static SimpleTerm<'_>>],
+    ) {
+        let ([s, _, o], _) = quad
+            .map_err(|e| Error::Dataset(DatasetError::Dataset(e)))?
+            .to_spog();
+
+        let is_primary = dataset
+            .quads_matching(
+                matcher::Any,
+                matcher::Any,
+                [s.borrow_term()],
+                [None::<&'static SimpleTerm<'_>>],
+            )
+            .next()
+            .transpose()
+            .map_err(|e| Error::Dataset(DatasetError::Dataset(e)))?
+            .is_none();
+        if !is_primary {
+            continue;
+        }
+
+        if o.eq(expected_type) {
+            return Ok(());
+        }
+        found.extend(o.iri().map(|iri| iri.as_str().into()));
+    }
+
+    Err(Error::UnexpectedDocumentType { found })
 }
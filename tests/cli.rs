@@ -0,0 +1,152 @@
+//! Signs a document with the `ldsig2017` binary and verifies the output with the library.
+
+#![cfg(feature = "bin-ldsig2017")]
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use futures_executor::block_on;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::{Pkcs1v15Sign, RsaPublicKey};
+use rsa_signature_2017::json_ld::SignedDocument;
+use sha2::Sha256;
+use sophia_inmem::dataset::LightDataset;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+const DOCUMENT: &str = r#"
+    {
+        "@context": [
+            "https://w3id.org/security/v1",
+            {
+                "content": "https://www.w3.org/ns/activitystreams#content"
+            }
+        ],
+        "type": "https://www.w3.org/ns/activitystreams#Note",
+        "content": "Hello, world!"
+    }
+"#;
+
+fn sign(args: &[&str], input: &str) -> Output {
+    run(
+        Command::new(env!("CARGO_BIN_EXE_ldsig2017"))
+            .arg("sign")
+            .args(["--creator", "https://example.com/#me"])
+            .arg("--key")
+            .arg(Path::new(FIXTURES).join("key.private.pem"))
+            .args(args),
+        input,
+    )
+}
+
+fn run(command: &mut Command, input: &str) -> Output {
+    let mut child = command
+        .env_remove("LDSIG2017_KEY")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn sign_ok(args: &[&str], input: &str) -> String {
+    let output = sign(args, input);
+    assert!(
+        output.status.success(),
+        "ldsig2017 failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn public_key() -> RsaPublicKey {
+    let key = fs::read_to_string(Path::new(FIXTURES).join("key.pem")).unwrap();
+    RsaPublicKey::from_public_key_pem(&key).unwrap()
+}
+
+#[test]
+fn sign_then_verify() {
+    let signed = sign_ok(&[], DOCUMENT);
+
+    let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+        &signed, None,
+    ))
+    .unwrap_or_else(|e| panic!("unable to parse the output: {:?}\n{}", e, signed));
+    let [ref signature] = *document.signatures() else {
+        panic!("expected exactly one signature:\n{}", signed);
+    };
+    assert_eq!(
+        signature.creator().unwrap().unwrap().as_str(),
+        "https://example.com/#me",
+    );
+    document.verify_rsa_signature_2017(&public_key()).unwrap();
+}
+
+#[test]
+fn replace() {
+    let signed = sign_ok(&[], DOCUMENT);
+
+    let output = sign(&["--no-replace"], &signed);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already has a signature"));
+
+    // The last flag wins.
+    let resigned = sign_ok(&["--no-replace", "--replace"], &signed);
+    let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+        &resigned, None,
+    ))
+    .unwrap();
+    assert_eq!(document.signatures().len(), 1);
+    document.verify_rsa_signature_2017(&public_key()).unwrap();
+}
+
+#[test]
+fn signature_encoding() {
+    let signed = sign_ok(&["--signature-encoding", "base64url"], DOCUMENT);
+    assert!(!signed.contains("=\""), "unexpected padding:\n{}", signed);
+
+    let document = block_on(SignedDocument::<LightDataset>::parse_json_str(
+        &signed, None,
+    ))
+    .unwrap();
+    document.verify_rsa_signature_2017(&public_key()).unwrap();
+}
+
+#[test]
+fn hash() {
+    const OPTIONS: [&str; 4] = ["--created", "2024-01-01T00:00:00Z", "--nonce", "deadbeef"];
+
+    let output = run(
+        Command::new(env!("CARGO_BIN_EXE_ldsig2017"))
+            .arg("hash")
+            .args(["--creator", "https://example.com/#me"])
+            .args(OPTIONS),
+        DOCUMENT,
+    );
+    assert!(
+        output.status.success(),
+        "ldsig2017 failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let hash = hex::decode(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+
+    // The hash is exactly what `sign` signs with the same options.
+    let signed: serde_json::Value = serde_json::from_str(&sign_ok(&OPTIONS, DOCUMENT)).unwrap();
+    let signature_value = STANDARD
+        .decode(signed["signature"]["signatureValue"].as_str().unwrap())
+        .unwrap();
+    public_key()
+        .verify(Pkcs1v15Sign::new::<Sha256>(), &hash, &signature_value)
+        .unwrap();
+}
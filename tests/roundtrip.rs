@@ -0,0 +1,162 @@
+//! Property tests checking that signing then verifying round-trips for arbitrary datasets.
+
+#![cfg(all(feature = "json-ld", feature = "thread-rng"))]
+
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::RsaPrivateKey;
+use rsa_signature_2017::{verify, verify_rsa_signature_2017, SignOptions};
+use sophia_api::dataset::{Dataset, MutableDataset};
+use sophia_api::ns::xsd;
+use sophia_api::quad::{Quad as _, Spog};
+use sophia_api::term::{BnodeId, IriRef, LanguageTag, SimpleTerm, Term};
+use sophia_inmem::dataset::LightDataset;
+use sophia_iri::Iri;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn key() -> &'static RsaPrivateKey {
+    static KEY: OnceLock<RsaPrivateKey> = OnceLock::new();
+    KEY.get_or_init(|| {
+        let pem = fs::read_to_string(Path::new(FIXTURES).join("key.private.pem")).unwrap();
+        RsaPrivateKey::from_pkcs8_pem(&pem).unwrap()
+    })
+}
+
+fn iri() -> impl Strategy<Value = SimpleTerm<'static>> {
+    (0..4u8).prop_map(|i| {
+        SimpleTerm::Iri(IriRef::new_unchecked(
+            format!("https://example.com/{}", i).into(),
+        ))
+    })
+}
+
+fn bnode() -> impl Strategy<Value = SimpleTerm<'static>> {
+    (0..4u8).prop_map(|i| SimpleTerm::BlankNode(BnodeId::new_unchecked(format!("b{}", i).into())))
+}
+
+fn literal() -> impl Strategy<Value = SimpleTerm<'static>> {
+    // Control characters are excluded as they don't survive the canonicalization (see the
+    // crate-level documentation).
+    let lexical = "[^\\p{Cc}]{0,16}";
+    prop_oneof![
+        lexical.prop_map(|value| SimpleTerm::LiteralDatatype(
+            value.into(),
+            xsd::string.iri().unwrap(),
+        )),
+        (lexical, "[a-z]{2}(-[A-Z]{2})?").prop_map(|(value, tag)| SimpleTerm::LiteralLanguage(
+            value.into(),
+            LanguageTag::new_unchecked(tag.into()),
+        )),
+    ]
+}
+
+type OwnedQuad = Spog<SimpleTerm<'static>>;
+
+fn quad() -> impl Strategy<Value = OwnedQuad> {
+    let subject = prop_oneof![iri(), bnode()];
+    let predicate = (0..4u8).prop_map(|i| {
+        SimpleTerm::Iri(IriRef::new_unchecked(
+            format!("https://example.com/p{}", i).into(),
+        ))
+    });
+    let object = prop_oneof![iri(), bnode(), literal()];
+    let graph = prop::option::of(prop_oneof![iri(), bnode()]);
+    (subject, predicate, object, graph).prop_map(|(s, p, o, g)| ([s, p, o], g))
+}
+
+fn dataset() -> impl Strategy<Value = Vec<OwnedQuad>> {
+    prop::collection::vec(quad(), 1..8).prop_map(|quads| {
+        // Deduplicate the quads so that a mutation of any single one changes the dataset.
+        let mut dataset = LightDataset::new();
+        for ([s, p, o], g) in &quads {
+            dataset.insert(s, p, o, g.as_ref()).unwrap();
+        }
+        dataset
+            .quads()
+            .map(|quad| {
+                let ([s, p, o], g) = quad.unwrap().to_spog();
+                (
+                    [s.into_term(), p.into_term(), o.into_term()],
+                    g.map(|g| g.into_term()),
+                )
+            })
+            .collect()
+    })
+}
+
+fn created() -> impl Strategy<Value = String> {
+    (
+        1970..2100u32,
+        1..=12u32,
+        1..=28u32,
+        0..24u32,
+        0..60u32,
+        0..60u32,
+    )
+        .prop_map(|(year, month, day, hour, minute, second)| {
+            format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                year, month, day, hour, minute, second
+            )
+        })
+}
+
+fn to_dataset<'a>(quads: impl IntoIterator<Item = &'a OwnedQuad>) -> LightDataset {
+    let mut dataset = LightDataset::new();
+    for ([s, p, o], g) in quads {
+        dataset.insert(s, p, o, g.as_ref()).unwrap();
+    }
+    dataset
+}
+
+proptest! {
+    // Signing with a 2048-bit key is slow in the debug profile.
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn sign_then_verify(
+        quads in dataset(),
+        created in created(),
+        nonce in prop::option::of("[0-9a-f]{8,32}"),
+        domain in prop::option::of("[a-z]{1,10}\\.example"),
+        mutated in any::<Index>(),
+    ) {
+        let dataset = to_dataset(&quads);
+        let signature = <SignOptions<'_, '_>>::new()
+            .created(&*created)
+            .nonce(nonce.as_deref())
+            .domain(domain.as_deref())
+            .sign_rsa_signature_2017(
+                &dataset,
+                key(),
+                Iri::new_unchecked("https://example.com/#me"),
+            )
+            .unwrap();
+        let options = signature.options_dataset(None);
+
+        verify_rsa_signature_2017(&dataset, &options, key(), &signature.signature_value).unwrap();
+
+        // Replace the object of a quad with a literal that the strategies never generate.
+        let i = mutated.index(quads.len());
+        let mut quads = quads;
+        quads[i].0[2] = SimpleTerm::LiteralDatatype(
+            "42".into(),
+            xsd::integer.iri().unwrap(),
+        );
+        let tampered = to_dataset(&quads);
+
+        let result =
+            verify_rsa_signature_2017(&tampered, &options, key(), &signature.signature_value);
+        prop_assert!(
+            matches!(result, Err(verify::Error::Verification(_))),
+            "unexpected result: {:?}",
+            result,
+        );
+    }
+}